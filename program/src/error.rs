@@ -32,6 +32,99 @@ pub enum AuctionError {
     /// No Bidder Found
     #[error("No Bidder Found")]
     NoBidderFound,
+    /// FT Mint Mismatch
+    #[error("FT Mint Mismatch")]
+    FtMintMismatch,
+    /// NFT Mint Mismatch
+    #[error("NFT Mint Mismatch")]
+    NftMintMismatch,
+    /// Invalid Mint
+    #[error("Invalid Mint")]
+    InvalidMint,
+    /// Account Mismatch
+    #[error("Account Mismatch")]
+    AccountMismatch,
+    /// Invalid Escrow Account
+    #[error("Invalid Escrow Account")]
+    InvalidEscrowAccount,
+    /// Invalid PDA Account
+    #[error("Invalid PDA Account")]
+    InvalidPdaAccount,
+    /// Invalid Sysvar
+    #[error("Invalid Sysvar")]
+    InvalidSysvar,
+    /// Invalid Token Program
+    #[error("Invalid Token Program")]
+    InvalidTokenProgram,
+    /// Invalid System Program
+    #[error("Invalid System Program")]
+    InvalidSystemProgram,
+    /// Invalid Token Account
+    #[error("Invalid Token Account")]
+    InvalidTokenAccount,
+    /// Token Account Owner Mismatch
+    #[error("Token Account Owner Mismatch")]
+    TokenAccountOwnerMismatch,
+    /// Reserve Not Revealed
+    #[error("Reserve Not Revealed")]
+    ReserveNotRevealed,
+    /// Invalid Reserve Reveal
+    #[error("Invalid Reserve Reveal")]
+    InvalidReserveReveal,
+    /// Insufficient Bid Increment
+    #[error("Insufficient Bid Increment")]
+    InsufficientBidIncrement,
+    /// Not Started
+    #[error("Not Started")]
+    NotStarted,
+    /// Buy Now Unavailable
+    #[error("Buy Now Unavailable")]
+    BuyNowUnavailable,
+    /// Invalid Auction Kind
+    #[error("Invalid Auction Kind")]
+    InvalidAuctionKind,
+    /// Invalid Bid Account
+    #[error("Invalid Bid Account")]
+    InvalidBidAccount,
+    /// Invalid Bid Reveal
+    #[error("Invalid Bid Reveal")]
+    InvalidBidReveal,
+    /// Insufficient Deposit
+    #[error("Insufficient Deposit")]
+    InsufficientDeposit,
+    /// Bid Not Withdrawable
+    #[error("Bid Not Withdrawable")]
+    BidNotWithdrawable,
+    /// Lot Quantity Mismatch
+    #[error("Lot Quantity Mismatch")]
+    LotQuantityMismatch,
+    /// Invalid Bundle
+    #[error("Invalid Bundle")]
+    InvalidBundle,
+    /// Invalid Authority
+    #[error("Invalid Authority")]
+    InvalidAuthority,
+    /// Invalid Fee Config
+    #[error("Invalid Fee Config")]
+    InvalidFeeConfig,
+    /// Invalid Metadata
+    #[error("Invalid Metadata")]
+    InvalidMetadata,
+    /// Fees Exceed Price
+    #[error("Fees Exceed Price")]
+    FeesExceedPrice,
+    /// Invalid Program Config
+    #[error("Invalid Program Config")]
+    InvalidProgramConfig,
+    /// Program Paused
+    #[error("Program Paused")]
+    ProgramPaused,
+    /// Invalid Auction House
+    #[error("Invalid Auction House")]
+    InvalidAuctionHouse,
+    /// Currency Not Allowed
+    #[error("Currency Not Allowed")]
+    CurrencyNotAllowed,
 }
 
 impl From<AuctionError> for ProgramError {
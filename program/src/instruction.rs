@@ -1,5 +1,7 @@
 use crate::error::AuctionError::InvalidInstruction;
+use crate::state::{AuctionKind, DecayCurve};
 use solana_program::program_error::ProgramError;
+use solana_program::pubkey::Pubkey;
 
 pub enum AuctionInstruction {
     /// Starts the auction by creating and populating an escrow account and transferring ownership of the given temp NFT account to the PDA
@@ -7,18 +9,40 @@ pub enum AuctionInstruction {
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]` The account of the person starting the auction
-    /// 1. `[writable]` Temporary NFT account that should be created prior to this instruction and owned by the exhibitor
-    /// 2. `[]` The exhibitor's NFT account for the token they will receive should the trade go through
-    /// 3. `[writable]` The escrow account, it will hold all necessary info about the auction.
-    /// 4. `[]` The rent sysvar
-    /// 4. `[]` The clock sysvar
-    /// 5. `[]` The token program
+    /// 0. `[signer, writable]` The account of the person starting the auction, it pays the rent of the escrow account
+    /// 1. `[writable]` The exhibitor's NFT account to send the exhibiting NFT from
+    /// 2. `[writable]` Temporary NFT account that should be created prior to this instruction and owned by the exhibitor
+    /// 3. `[]` The exhibitor's FT account for the token they will receive should the trade go through,
+    ///    or the account to receive the lamports for the native SOL auction.
+    ///    It can be the exhibitor's wallet to unwrap the wrapped SOL to for the English auction without the buy-now price.
+    /// 4. `[]` The mint of the FT accepted for bidding, or the vault account for the native SOL auction.
+    ///    The vault must be the PDA derived from `["vault", escrow]` and is created by this instruction.
+    /// 5. `[writable]` The escrow account, it will hold all necessary info about the auction.
+    ///    It must be the PDA derived from `["auction", auction house, exhibitor, NFT mint]` and is created by this instruction,
+    ///    where the auction house is the default pubkey for the auction outside of any house.
+    /// 6. `[]` The rent sysvar
+    /// 7. `[]` The clock sysvar
+    /// 8. `[]` The token program
+    /// 9. `[]` The system program
+    /// 10. `[writable]` The bid book account, only for the uniform-price auction.
+    ///     It must be the PDA derived from `["book", escrow]` and is created by this instruction.
+    /// 11. `[]` The fee config account of the auction currency, derived from `["fee", mint]`.
+    ///     It must be configured by `SetFeeConfig` in advance, even when no fee is charged.
+    ///     The currency is the FT mint, the native mint for the native SOL auction, or the NFT mint for the reverse auction.
+    ///     It's the auction house account instead to exhibit the auction in the house, which must allow the currency and charges its own fee.
+    /// 12. `[]` The metadata account of the NFT of the Metaplex Token Metadata program, which may not exist, except for the reverse auction.
+    ///     The royalties and the verified creators are saved at this time, and the fees and the royalties must not exceed the price.
+    ///
+    /// Followed by the pairs of the accounts below for each NFT exhibited in the lot in addition to the exhibiting NFT, up to 4 NFTs.
+    /// The uniform-price auction can't exhibit them.
+    ///
+    /// - `[writable]` Temporary NFT account that should be created prior to this instruction and owned by the exhibitor
+    /// - `[writable]` The exhibitor's NFT account to send the NFT from
+    ///
+    /// The last account is the program config account derived from `["config"]`, and the auction can't be exhibited while the program is paused.
     Exhibit {
-        /// Initial NFT price
-        initial_price: u64,
-        /// Auction duration
-        seconds: u64,
+        /// Parameters of the auction
+        args: ExhibitArgs,
     },
 
     /// Bid on the auction and transfer ownership of the given temp FT account to the PDA
@@ -26,15 +50,30 @@ pub enum AuctionInstruction {
     ///
     /// Accounts expected:
     ///
-    /// 0. `[signer]`  The account of the person bidding on the auction
-    /// 1. `[writable]` The current highest bidder's temporary FT account
-    /// 2. `[writable]` The current highest bidder's FT account to get back to when the other person become the highest bidder
-    /// 3. `[writable]` The bidder's temporary FT account for depositing FT in escrow
-    /// 4. `[writable]` The bidder's FT account to get back to when the other person become the highest bidder
-    /// 5. `[writable]` The escrow account, it will hold all necessary info about the auction.
-    /// 6. `[]` The clock sysvar
-    /// 7. `[]` The token program
-    /// 8. `[]` The PDA account
+    /// 0. `[signer]` The account of the person bidding on the auction
+    /// 1. `[writable]` The current highest bidder's account to get back the rent of the temporary FT account
+    /// 2. `[writable]` The current highest bidder's temporary FT account
+    /// 3. `[writable]` The current highest bidder's FT account to get back to when the other person become the highest bidder
+    /// 4. `[writable]` The bidder's temporary FT account for depositing FT in escrow
+    /// 5. `[writable]` The bidder's FT account to get back to when the other person become the highest bidder
+    /// 6. `[]` The bidder's NFT account to receive the NFT when the auction is settled
+    /// 7. `[writable]` The escrow account, it will hold all necessary info about the auction.
+    /// 8. `[]` The clock sysvar
+    /// 9. `[]` The token program
+    /// 10. `[]` The PDA account
+    /// 11. `[writable]` The bid book account, only for the uniform-price auction.
+    ///     The accounts of the current highest bidder are the ones of the lowest bidder in the bid book when it's full.
+    /// 11. `[]` The system program, only for the native SOL auction.
+    ///     Then both temporary FT accounts are the vault account and the FT accounts are the wallets of the bidders.
+    /// 11. `[]` The system program, only when bidding in lamports on the auction priced in wrapped SOL.
+    ///     Then the bidder's FT account is the bidder's wallet, and the temporary FT account is the wSOL account
+    ///     derived from `["wsol", escrow, bidder]`, which is created by this instruction.
+    /// 12. `[]` The mint of the wrapped SOL, only when bidding in lamports on the auction priced in wrapped SOL
+    ///
+    /// Optionally followed by the referrer's FT account holding the auction currency, to receive the referral fee when the bid wins.
+    /// The uniform-price auction doesn't accept any referrer.
+    ///
+    /// The last account is the program config account derived from `["config"]`, and the bid can't be placed while the program is paused.
     Bid {
         /// Bidding price
         price: u64,
@@ -52,10 +91,18 @@ pub enum AuctionInstruction {
     /// 3. `[writable]` The escrow account holding the escrow info
     /// 4. `[]` The token program
     /// 5. `[]` The PDA account
+    /// 6. `[writable]` The bid book account, only for the uniform-price auction
+    /// 6. `[writable]` The vault account, only for the native SOL auction
+    ///
+    /// Followed by the pairs of the accounts below for each NFT in the lot in addition to the exhibiting NFT
+    ///
+    /// - `[writable]` The PDA's temporary NFT account
+    /// - `[writable]` The exhibitor's NFT account to get the NFT back to
     Cancel {},
 
     /// Closes a auction
     /// Only the successful bidder can close the auction
+    /// If the highest price doesn't reach the reserve price, the FT is returned to the bidder and the NFT is returned to the exhibitor
     ///
     ///
     /// Accounts expected:
@@ -70,7 +117,416 @@ pub enum AuctionInstruction {
     /// 7. `[]` The clock sysvar
     /// 8. `[]` The token program
     /// 9. `[]` The PDA account
-    Close {},
+    /// 10. `[writable]` The successful bidder's FT account to get back to when the reserve price is not met
+    /// 11. `[writable]` The exhibitor's NFT account to get back to when the reserve price is not met
+    ///
+    /// Followed by the optional accounts below in this order, each of which is given only when its condition holds
+    ///
+    /// - `[writable]` The successful bidder's sealed bid account, only for the sealed bid auction
+    /// - `[]` The system program, only when the wrapped SOL is unwrapped to the exhibitor's wallet
+    /// - `[writable]` The treasury account to receive the marketplace fee, only when the fee is charged. The fee is left to the exhibitor if it is closed or frozen
+    /// - `[writable]` The FT account of each verified creator saved at the time of exhibiting to receive the royalties, in the order of the creators.
+    ///   It must hold the auction currency, which is the wrapped SOL for the native SOL auction.
+    /// - `[writable]` The referrer's FT account given at the time of bidding, only when the successful bidder was referred and the referral fee is charged. No referral fee is paid if it is closed or frozen
+    ///
+    /// The temporary FT account is the vault account and the FT accounts are the wallets for the native SOL auction.
+    ///
+    /// Then followed by the pairs of the accounts below for each NFT in the lot in addition to the exhibiting NFT
+    ///
+    /// - `[writable]` The PDA's temporary NFT account
+    /// - `[writable]` The successful bidder's NFT account to receive the NFT, or the exhibitor's one when the reserve price is not met
+    Close {
+        /// Hidden reserve price and its salt, required when the reserve price is hidden
+        reserve_reveal: Option<ReserveReveal>,
+    },
+
+    /// Settles a auction on behalf of the successful bidder
+    /// Anyone can settle the auction after it ends, and the NFT is sent to the account specified by the successful bidder at the time of bidding
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The account of the successful bidder to close the temporary FT account
+    /// 1. `[writable]` The account ot the person started the auction to close the escrow
+    /// 2. `[writable]` The temporary NFT account to send to successful bidder
+    /// 3. `[writable]` The FT account to sent FT to the exhibitor'
+    /// 4. `[writable]` The temporary FT account that holds the successful bidder's FT
+    /// 5. `[writable]` The NFT account specified by the successful bidder at the time of bidding
+    /// 6. `[writable]` The escrow account holding the escrow info
+    /// 7. `[]` The clock sysvar
+    /// 8. `[]` The token program
+    /// 9. `[]` The PDA account
+    /// 10. `[writable]` The successful bidder's FT account to get back to when the reserve price is not met
+    /// 11. `[writable]` The exhibitor's NFT account to get back to when the reserve price is not met
+    ///
+    /// Followed by the optional accounts below in this order, each of which is given only when its condition holds
+    ///
+    /// - `[writable]` The successful bidder's sealed bid account, only for the sealed bid auction
+    /// - `[]` The system program, only when the wrapped SOL is unwrapped to the exhibitor's wallet
+    /// - `[writable]` The treasury account to receive the marketplace fee, only when the fee is charged. The fee is left to the exhibitor if it is closed or frozen
+    /// - `[writable]` The FT account of each verified creator saved at the time of exhibiting to receive the royalties, in the order of the creators.
+    ///   It must hold the auction currency, which is the wrapped SOL for the native SOL auction.
+    /// - `[writable]` The referrer's FT account given at the time of bidding, only when the successful bidder was referred and the referral fee is charged. No referral fee is paid if it is closed or frozen
+    ///
+    /// The temporary FT account is the vault account and the FT accounts are the wallets for the native SOL auction.
+    ///
+    /// Then followed by the pairs of the accounts below for each NFT in the lot in addition to the exhibiting NFT
+    ///
+    /// - `[writable]` The PDA's temporary NFT account
+    /// - `[writable]` The successful bidder's NFT account to receive the NFT, or the exhibitor's one when the reserve price is not met
+    Settle {
+        /// Hidden reserve price and its salt, required when the reserve price is hidden
+        reserve_reveal: Option<ReserveReveal>,
+    },
+
+    /// Reclaims the NFT of a auction that ended without any bidder
+    /// Anyone can reclaim the auction, and the NFT is returned to the account it was exhibited from
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The account of the person started the auction to close the escrow
+    /// 1. `[writable]` The PDA's temporary NFT account
+    /// 2. `[writable]` The exhibitor's NFT account the NFT was exhibited from
+    /// 3. `[writable]` The escrow account holding the escrow info
+    /// 4. `[]` The clock sysvar
+    /// 5. `[]` The token program
+    /// 6. `[]` The PDA account
+    /// 7. `[writable]` The bid book account, only for the uniform-price auction
+    /// 7. `[writable]` The vault account, only for the native SOL auction
+    ///
+    /// Followed by the pairs of the accounts below for each NFT in the lot in addition to the exhibiting NFT
+    ///
+    /// - `[writable]` The PDA's temporary NFT account
+    /// - `[writable]` The exhibitor's NFT account to get the NFT back to
+    Reclaim {},
+
+    /// Buys the NFT at the buy-now price and ends the auction immediately
+    /// The current highest bidder, if any, gets back the deposited FT
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person buying the NFT
+    /// 1. `[writable]` The buyer's FT account to pay the buy-now price from
+    /// 2. `[writable]` The buyer's NFT account to receive the NFT
+    /// 3. `[writable]` The account of the person started the auction to close the escrow
+    /// 4. `[writable]` The exhibitor's FT account to receive the buy-now price
+    /// 5. `[writable]` The PDA's temporary NFT account
+    /// 6. `[writable]` The current highest bidder's account to get back the rent of the temporary FT account, the default pubkey if there is no bidder
+    /// 7. `[writable]` The current highest bidder's temporary FT account, the default pubkey if there is no bidder
+    /// 8. `[writable]` The current highest bidder's FT account to get back to, the default pubkey if there is no bidder
+    /// 9. `[writable]` The escrow account holding the escrow info
+    /// 10. `[]` The clock sysvar
+    /// 11. `[]` The token program
+    /// 12. `[]` The PDA account
+    ///
+    /// Followed by the optional accounts below in this order, each of which is given only when its condition holds
+    ///
+    /// - `[]` The system program, only for the native SOL auction.
+    ///   Then the buyer's FT account is the buyer's wallet and the FT accounts to receive are the wallets.
+    /// - `[writable]` The vault account, only for the native SOL auction
+    /// - `[writable]` The treasury account to receive the marketplace fee, only when the fee is charged. The fee is left to the exhibitor if it is closed or frozen
+    /// - `[writable]` The FT account of each verified creator saved at the time of exhibiting to receive the royalties, in the order of the creators.
+    ///   It must hold the auction currency, which is the wrapped SOL for the native SOL auction.
+    ///
+    /// Then followed by the pairs of the accounts below for each NFT in the lot in addition to the exhibiting NFT
+    ///
+    /// - `[writable]` The PDA's temporary NFT account
+    /// - `[writable]` The buyer's NFT account to receive the NFT
+    ///
+    /// The last account is the program config account derived from `["config"]`, and the NFT can't be bought while the program is paused.
+    BuyNow {},
+
+    /// Buys the NFT of the Dutch auction at the current price and ends the auction immediately
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person buying the NFT
+    /// 1. `[writable]` The buyer's FT account to pay the price from
+    /// 2. `[writable]` The buyer's NFT account to receive the NFT
+    /// 3. `[writable]` The account of the person started the auction to close the escrow
+    /// 4. `[writable]` The exhibitor's FT account to receive the price
+    /// 5. `[writable]` The PDA's temporary NFT account
+    /// 6. `[writable]` The escrow account holding the escrow info
+    /// 7. `[]` The clock sysvar
+    /// 8. `[]` The token program
+    /// 9. `[]` The PDA account
+    ///
+    /// Followed by the optional accounts below in this order, each of which is given only when its condition holds
+    ///
+    /// - `[]` The system program, only for the native SOL auction.
+    ///   Then the buyer's FT account is the buyer's wallet and the FT account to receive is the exhibitor's wallet.
+    /// - `[writable]` The vault account, only for the native SOL auction
+    /// - `[writable]` The treasury account to receive the marketplace fee, only when the fee is charged. The fee is left to the exhibitor if it is closed or frozen
+    /// - `[writable]` The FT account of each verified creator saved at the time of exhibiting to receive the royalties, in the order of the creators.
+    ///   It must hold the auction currency, which is the wrapped SOL for the native SOL auction.
+    ///
+    /// Then followed by the pairs of the accounts below for each NFT in the lot in addition to the exhibiting NFT
+    ///
+    /// - `[writable]` The PDA's temporary NFT account
+    /// - `[writable]` The buyer's NFT account to receive the NFT
+    ///
+    /// The last account is the program config account derived from `["config"]`, and the NFT can't be bought while the program is paused.
+    Buy {
+        /// Maximum price the buyer accepts to pay
+        price: u64,
+    },
+
+    /// Commits a sealed bid on the auction during the commit phase and transfers ownership of the given temp FT account to the PDA
+    /// Each bidder can commit only once, and the deposit must cover the bidding price
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The account of the person bidding on the auction, it pays the rent of the sealed bid account
+    /// 1. `[writable]` The sealed bid account, it must be the PDA derived from `["bid", escrow, bidder]` and is created by this instruction
+    /// 2. `[writable]` The bidder's temporary FT account for depositing FT in escrow
+    /// 3. `[writable]` The bidder's FT account to send the deposit from and get back to
+    /// 4. `[]` The bidder's NFT account to receive the NFT when the bid wins
+    /// 5. `[writable]` The escrow account holding the escrow info
+    /// 6. `[]` The rent sysvar
+    /// 7. `[]` The clock sysvar
+    /// 8. `[]` The token program
+    /// 9. `[]` The system program
+    /// 10. `[]` The PDA account
+    /// 11. `[]` The program config account derived from `["config"]`, and the bid can't be committed while the program is paused
+    CommitBid {
+        /// Hash of the bidding price and a salt (`hashv(&[price_le_bytes, salt])`)
+        commitment: [u8; 32],
+        /// Amount of FT to deposit
+        deposit: u64,
+    },
+
+    /// Reveals the sealed bid during the reveal phase
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The account of the person bidding on the auction
+    /// 1. `[writable]` The sealed bid account
+    /// 2. `[writable]` The escrow account holding the escrow info
+    /// 3. `[]` The clock sysvar
+    RevealBid {
+        /// Bidding price committed
+        price: u64,
+        /// Salt used to hash the bidding price
+        salt: [u8; 32],
+    },
+
+    /// Withdraws the deposit of the sealed bid that didn't win and closes the sealed bid account
+    /// Anyone can withdraw after the reveal phase, and the deposit of the bid not revealed is forfeited to the exhibitor if the auction says so
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The account of the bidder to get back the rent
+    /// 1. `[writable]` The sealed bid account
+    /// 2. `[writable]` The bidder's temporary FT account holding the deposit
+    /// 3. `[writable]` The bidder's FT account to get the deposit back to
+    /// 4. `[writable]` The exhibitor's FT account to forfeit the deposit to
+    /// 5. `[]` The escrow account, which may be already closed
+    /// 6. `[]` The clock sysvar
+    /// 7. `[]` The token program
+    /// 8. `[]` The PDA account
+    WithdrawBid {},
+
+    /// Settles the uniform-price auction
+    /// Anyone can settle the auction after it ends, and each winner gets a unit at the clearing price
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[writable]` The account of the person started the auction to close the escrow
+    /// 1. `[writable]` The PDA's temporary NFT account
+    /// 2. `[writable]` The exhibitor's FT account to receive the FT
+    /// 3. `[writable]` The exhibitor's NFT account to get back the units not sold
+    /// 4. `[writable]` The escrow account holding the escrow info
+    /// 5. `[writable]` The bid book account
+    /// 6. `[]` The clock sysvar
+    /// 7. `[]` The token program
+    /// 8. `[]` The PDA account
+    /// 9. `[writable]` The treasury account to receive the marketplace fee, only when the fee is charged. The fee is left to the exhibitor if it is closed or frozen
+    /// 10. `[writable]` The FT account of each verified creator saved at the time of exhibiting to receive the royalties, in the order of the creators
+    /// 11. For each bid in the order of the bid book:
+    ///     `[writable]` The account of the bidder to get back the rent of the temporary FT account,
+    ///     `[writable]` The bidder's temporary FT account,
+    ///     `[writable]` The bidder's FT account to get back the difference from the clearing price,
+    ///     `[writable]` The bidder's NFT account to receive a unit
+    SettleUniform {},
+
+    /// Sets the marketplace fee charged on the proceeds of the auctions priced in the given FT mint, and the referral fee paid out of it or the proceeds
+    /// Only the admin in the program config can set it, and the auctions exhibited before keep the fee at that time
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The admin, it pays the rent of the fee config account
+    /// 1. `[writable]` The fee config account, it must be the PDA derived from `["fee", mint]` and is created by this instruction if not exists
+    /// 2. `[]` The FT mint
+    /// 3. `[]` The treasury FT account to receive the fee
+    /// 4. `[]` The program config account derived from `["config"]`
+    /// 5. `[]` The rent sysvar
+    /// 6. `[]` The system program
+    SetFeeConfig {
+        /// Fee in basis points of the proceeds
+        fee_bps: u16,
+        /// Referral fee in basis points paid to the referrer of the successful bidder
+        referral_bps: u16,
+        /// Whether the referral fee is a share of the marketplace fee rather than of the proceeds
+        referral_from_fee: bool,
+    },
+
+    /// Initializes the program config with the upgrade authority of this program as the admin
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The upgrade authority of this program, it pays the rent of the program config account
+    /// 1. `[writable]` The program config account, it must be the PDA derived from `["config"]` and is created by this instruction
+    /// 2. `[]` The program data account of this program
+    /// 3. `[]` The rent sysvar
+    /// 4. `[]` The system program
+    InitializeConfig {},
+
+    /// Pauses or resumes exhibiting, bidding and buying in an emergency
+    /// The auctions already started can always be cancelled, closed, settled and refunded
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The admin
+    /// 1. `[writable]` The program config account
+    SetPaused {
+        /// Whether exhibiting, bidding and buying are paused
+        paused: bool,
+    },
+
+    /// Proposes the new admin, who becomes the admin by accepting it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The admin
+    /// 1. `[writable]` The program config account
+    TransferAdmin {
+        /// Wallet pubkey of the new admin
+        new_admin: Pubkey,
+    },
+
+    /// Accepts the transfer of the admin authority
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The new admin proposed by the current one
+    /// 1. `[writable]` The program config account
+    AcceptAdmin {},
+
+    /// Creates the auction house run by the given authority
+    /// Only the admin in the program config can create it, so that every auction house is approved by the marketplace
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer, writable]` The admin, it pays the rent of the auction house account
+    /// 1. `[]` The authority of the auction house
+    /// 2. `[writable]` The auction house account, it must be the PDA derived from `["house", authority]` and is created by this instruction
+    /// 3. `[]` The program config account derived from `["config"]`
+    /// 4. `[]` The rent sysvar
+    /// 5. `[]` The system program
+    CreateAuctionHouse {
+        /// Fee in basis points of the proceeds
+        fee_bps: u16,
+        /// Referral fee in basis points paid to the referrer of the successful bidder
+        referral_bps: u16,
+        /// Whether the referral fee is a share of the marketplace fee rather than of the proceeds
+        referral_from_fee: bool,
+    },
+
+    /// Updates the fee of the auction house, which applies to the auctions exhibited after it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The authority of the auction house
+    /// 1. `[writable]` The auction house account
+    UpdateAuctionHouse {
+        /// Fee in basis points of the proceeds
+        fee_bps: u16,
+        /// Referral fee in basis points paid to the referrer of the successful bidder
+        referral_bps: u16,
+        /// Whether the referral fee is a share of the marketplace fee rather than of the proceeds
+        referral_from_fee: bool,
+    },
+
+    /// Allows or disallows the currency in the auction house, or changes the treasury account of the house in it
+    ///
+    ///
+    /// Accounts expected:
+    ///
+    /// 0. `[signer]` The authority of the auction house
+    /// 1. `[writable]` The auction house account
+    /// 2. `[]` The mint of the currency
+    /// 3. `[]` The treasury account of the house to receive the fee in the currency
+    SetHouseCurrency {
+        /// Whether the auctions in the house can be priced in the currency
+        allowed: bool,
+    },
+}
+
+/// Parameters of the auction given at the time of exhibiting
+pub struct ExhibitArgs {
+    /// Initial NFT price, which is the ceiling price escrowed as the budget of the reverse auction
+    pub initial_price: u64,
+    /// Auction duration
+    pub seconds: u64,
+    /// Visible reserve price, 0 if there is none
+    pub reserve_price: u64,
+    /// Hash of the hidden reserve price and a salt (`hashv(&[reserve_price_le_bytes, salt])`), all zeros if there is none
+    pub reserve_price_hash: [u8; 32],
+    /// Minimum amount by which a bid must exceed the current price
+    pub min_increment: u64,
+    /// Minimum rate in basis points by which a bid must exceed the current price
+    pub min_increment_bps: u16,
+    /// Bids placed within this many seconds before the end extend the auction, 0 if it never extends
+    pub extension_window: u64,
+    /// Seconds by which the end is extended on a late bid
+    pub extension_seconds: u64,
+    /// Maximum seconds by which the end can be extended in total, 0 if there is no limit
+    pub max_extension_seconds: u64,
+    /// Time bidding opens, 0 if it opens right away
+    pub start_at: i64,
+    /// Price at which anyone can buy the NFT immediately, 0 if there is none.
+    /// It can't be set together with a hidden reserve price.
+    pub buy_now_price: u64,
+    /// Kind of the auction
+    pub kind: AuctionKind,
+    /// Lowest price the Dutch auction decreases to from the initial price
+    pub floor_price: u64,
+    /// How the price of the Dutch auction decreases
+    pub decay_curve: DecayCurve,
+    /// Seconds between price decreases of the stepwise and exponential curves
+    pub decay_interval: u64,
+    /// Rate in basis points by which the price decreases at every interval of the exponential curve
+    pub decay_rate_bps: u16,
+    /// Seconds of the reveal phase following the commit phase of the sealed bid auction
+    pub reveal_seconds: u64,
+    /// Whether the deposit of the sealed bid not revealed is forfeited to the exhibitor, otherwise it's refunded
+    pub forfeit_unrevealed: bool,
+    /// Number of tokens of the exhibiting mint sold as one lot, or of the FT mint requested by the reverse auction
+    pub quantity: u64,
+    /// Whether the bids are paid in native lamports held in the vault account instead of the FT
+    pub native: bool,
+}
+
+/// Hidden reserve price revealed at settlement
+pub struct ReserveReveal {
+    /// Reserve price committed at the time of exhibiting
+    pub reserve_price: u64,
+    /// Salt used to hash the reserve price
+    pub salt: [u8; 32],
 }
 
 impl AuctionInstruction {
@@ -78,18 +534,118 @@ impl AuctionInstruction {
         let (instruction_type, rest) = input.split_first().ok_or(InvalidInstruction)?;
         Ok(match instruction_type {
             0 => Self::Exhibit {
-                initial_price: Self::unpack64(rest, 0)?,
-                seconds: Self::unpack64(rest, 8)?,
+                args: ExhibitArgs {
+                    initial_price: Self::unpack64(rest, 0)?,
+                    seconds: Self::unpack64(rest, 8)?,
+                    reserve_price: Self::unpack64(rest, 16)?,
+                    reserve_price_hash: Self::unpack32(rest, 24)?,
+                    min_increment: Self::unpack64(rest, 56)?,
+                    min_increment_bps: Self::unpack16(rest, 64)?,
+                    extension_window: Self::unpack64(rest, 66)?,
+                    extension_seconds: Self::unpack64(rest, 74)?,
+                    max_extension_seconds: Self::unpack64(rest, 82)?,
+                    start_at: Self::unpack64(rest, 90)? as i64,
+                    buy_now_price: Self::unpack64(rest, 98)?,
+                    kind: AuctionKind::try_from(Self::unpack8(rest, 106)?)
+                        .map_err(|_| InvalidInstruction)?,
+                    floor_price: Self::unpack64(rest, 107)?,
+                    decay_curve: DecayCurve::try_from(Self::unpack8(rest, 115)?)
+                        .map_err(|_| InvalidInstruction)?,
+                    decay_interval: Self::unpack64(rest, 116)?,
+                    decay_rate_bps: Self::unpack16(rest, 124)?,
+                    reveal_seconds: Self::unpack64(rest, 126)?,
+                    forfeit_unrevealed: Self::unpack8(rest, 134)? != 0,
+                    quantity: Self::unpack64(rest, 135)?,
+                    native: Self::unpack8(rest, 143)? != 0,
+                },
             },
             1 => Self::Bid {
                 price: Self::unpack64(rest, 0)?,
             },
             2 => Self::Cancel {},
-            3 => Self::Close {},
+            3 => Self::Close {
+                reserve_reveal: Self::unpack_reserve_reveal(rest)?,
+            },
+            4 => Self::Settle {
+                reserve_reveal: Self::unpack_reserve_reveal(rest)?,
+            },
+            5 => Self::Reclaim {},
+            6 => Self::BuyNow {},
+            7 => Self::Buy {
+                price: Self::unpack64(rest, 0)?,
+            },
+            8 => Self::CommitBid {
+                commitment: Self::unpack32(rest, 0)?,
+                deposit: Self::unpack64(rest, 32)?,
+            },
+            9 => Self::RevealBid {
+                price: Self::unpack64(rest, 0)?,
+                salt: Self::unpack32(rest, 8)?,
+            },
+            10 => Self::WithdrawBid {},
+            11 => Self::SettleUniform {},
+            12 => Self::SetFeeConfig {
+                fee_bps: Self::unpack16(rest, 0)?,
+                referral_bps: Self::unpack16(rest, 2)?,
+                referral_from_fee: Self::unpack8(rest, 4)? != 0,
+            },
+            13 => Self::InitializeConfig {},
+            14 => Self::SetPaused {
+                paused: Self::unpack8(rest, 0)? != 0,
+            },
+            15 => Self::TransferAdmin {
+                new_admin: Pubkey::new_from_array(Self::unpack32(rest, 0)?),
+            },
+            16 => Self::AcceptAdmin {},
+            17 => Self::CreateAuctionHouse {
+                fee_bps: Self::unpack16(rest, 0)?,
+                referral_bps: Self::unpack16(rest, 2)?,
+                referral_from_fee: Self::unpack8(rest, 4)? != 0,
+            },
+            18 => Self::UpdateAuctionHouse {
+                fee_bps: Self::unpack16(rest, 0)?,
+                referral_bps: Self::unpack16(rest, 2)?,
+                referral_from_fee: Self::unpack8(rest, 4)? != 0,
+            },
+            19 => Self::SetHouseCurrency {
+                allowed: Self::unpack8(rest, 0)? != 0,
+            },
             _ => return Err(InvalidInstruction.into()),
         })
     }
 
+    fn unpack_reserve_reveal(input: &[u8]) -> Result<Option<ReserveReveal>, ProgramError> {
+        if input.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(ReserveReveal {
+            reserve_price: Self::unpack64(input, 0)?,
+            salt: Self::unpack32(input, 8)?,
+        }))
+    }
+
+    fn unpack8(input: &[u8], start: usize) -> Result<u8, ProgramError> {
+        let v = *input.get(start).ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+
+    fn unpack16(input: &[u8], start: usize) -> Result<u16, ProgramError> {
+        let v = input
+            .get(start..start + 2)
+            .and_then(|slice| slice.try_into().ok())
+            .map(u16::from_le_bytes)
+            .ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+
+    fn unpack32(input: &[u8], start: usize) -> Result<[u8; 32], ProgramError> {
+        let v = input
+            .get(start..start + 32)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(InvalidInstruction)?;
+        Ok(v)
+    }
+
     fn unpack64(input: &[u8], start: usize) -> Result<u64, ProgramError> {
         let v = input
             .get(start..start + 8)
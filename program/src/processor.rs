@@ -1,21 +1,38 @@
 use crate::error::AuctionError;
-use crate::instruction::AuctionInstruction;
-use crate::state::Auction;
-use solana_program::account_info::{next_account_info, AccountInfo};
+use crate::instruction::{AuctionInstruction, ExhibitArgs, ReserveReveal};
+use crate::state::{
+    Auction, AuctionHouse, AuctionKind, BidBook, BidEntry, DecayCurve, FeeConfig, HouseCurrency,
+    ProgramConfig, SealedBid, BID_BOOK_SEED, BID_SEED, CONFIG_SEED, ESCROW_SEED, FEE_CONFIG_SEED,
+    HOUSE_SEED, MAX_HOUSE_CURRENCIES, MAX_UNIFORM_WINNERS, RESERVE_REVEAL_SECONDS, VAULT_SEED,
+    WSOL_SEED,
+};
+use crate::validation::{
+    split_program_config, AuctionHouseAccounts, BidAccounts, BidEntryAccounts, BundleItemAccounts,
+    BuyAccounts, BuyNowAccounts, CancelAccounts, CloseAccounts, CommitBidAccounts,
+    CreateAuctionHouseAccounts, ExhibitAccounts, HighestBidderAccounts, InitializeConfigAccounts,
+    ProgramConfigAccounts, ReclaimAccounts, RevealBidAccounts, RoyaltyAccounts,
+    SetFeeConfigAccounts, SettleUniformAccounts, WithdrawBidAccounts,
+};
+use solana_program::account_info::AccountInfo;
 use solana_program::clock::Clock;
 use solana_program::entrypoint::ProgramResult;
+use solana_program::hash::hashv;
 use solana_program::msg;
 use solana_program::program::{invoke, invoke_signed};
 use solana_program::program_error::ProgramError;
 use solana_program::program_pack::{IsInitialized, Pack};
 use solana_program::pubkey::Pubkey;
 use solana_program::rent::Rent;
+use solana_program::system_instruction;
+use solana_program::system_program;
 use solana_program::sysvar::Sysvar;
 use spl_token::state::Account as TokenAccount;
-use std::ops::Add;
 
 pub struct Processor;
 
+/// Payments out of the price to the accounts other than the exhibitor's, such as the marketplace fee and the royalties
+type Payments<'a, 'b> = Vec<(&'a AccountInfo<'b>, u64)>;
+
 impl Processor {
     pub fn process(
         // program_id is the public key for this program
@@ -29,65 +46,212 @@ impl Processor {
         // Decode byte array to AuctionInstruction struct.
         let instruction = AuctionInstruction::unpack(instruction_data)?;
         match instruction {
-            AuctionInstruction::Exhibit {
-                initial_price,
-                seconds,
-            } => {
+            AuctionInstruction::Exhibit { args } => {
                 msg!("Instruction: Exhibit");
-                Self::process_exhibit(accounts, initial_price, seconds, program_id)
+                let accounts = Self::check_not_paused(accounts, program_id)?;
+                Self::process_exhibit(accounts, args, program_id)
             }
             AuctionInstruction::Bid { price } => {
                 msg!("Instruction: Bid");
+                let accounts = Self::check_not_paused(accounts, program_id)?;
                 Self::process_bid(accounts, price, program_id)
             }
             AuctionInstruction::Cancel {} => {
                 msg!("Instruction: Cancel");
                 Self::process_cancel(accounts, program_id)
             }
-            AuctionInstruction::Close {} => {
+            AuctionInstruction::Close { reserve_reveal } => {
                 msg!("Instruction: Close ");
-                Self::process_close(accounts, program_id)
+                Self::process_close(accounts, reserve_reveal, program_id)
+            }
+            AuctionInstruction::Settle { reserve_reveal } => {
+                msg!("Instruction: Settle");
+                Self::process_settle(accounts, reserve_reveal, program_id)
+            }
+            AuctionInstruction::Reclaim {} => {
+                msg!("Instruction: Reclaim");
+                Self::process_reclaim(accounts, program_id)
+            }
+            AuctionInstruction::BuyNow {} => {
+                msg!("Instruction: BuyNow");
+                let accounts = Self::check_not_paused(accounts, program_id)?;
+                Self::process_buy_now(accounts, program_id)
+            }
+            AuctionInstruction::Buy { price } => {
+                msg!("Instruction: Buy");
+                let accounts = Self::check_not_paused(accounts, program_id)?;
+                Self::process_buy(accounts, price, program_id)
+            }
+            AuctionInstruction::CommitBid {
+                commitment,
+                deposit,
+            } => {
+                msg!("Instruction: CommitBid");
+                let accounts = Self::check_not_paused(accounts, program_id)?;
+                Self::process_commit_bid(accounts, commitment, deposit, program_id)
+            }
+            AuctionInstruction::RevealBid { price, salt } => {
+                msg!("Instruction: RevealBid");
+                Self::process_reveal_bid(accounts, price, salt, program_id)
+            }
+            AuctionInstruction::WithdrawBid {} => {
+                msg!("Instruction: WithdrawBid");
+                Self::process_withdraw_bid(accounts, program_id)
+            }
+            AuctionInstruction::SettleUniform {} => {
+                msg!("Instruction: SettleUniform");
+                Self::process_settle_uniform(accounts, program_id)
+            }
+            AuctionInstruction::SetFeeConfig {
+                fee_bps,
+                referral_bps,
+                referral_from_fee,
+            } => {
+                msg!("Instruction: SetFeeConfig");
+                Self::process_set_fee_config(
+                    accounts,
+                    fee_bps,
+                    referral_bps,
+                    referral_from_fee,
+                    program_id,
+                )
+            }
+            AuctionInstruction::InitializeConfig {} => {
+                msg!("Instruction: InitializeConfig");
+                Self::process_initialize_config(accounts, program_id)
+            }
+            AuctionInstruction::SetPaused { paused } => {
+                msg!("Instruction: SetPaused");
+                Self::process_set_paused(accounts, paused, program_id)
+            }
+            AuctionInstruction::TransferAdmin { new_admin } => {
+                msg!("Instruction: TransferAdmin");
+                Self::process_transfer_admin(accounts, new_admin, program_id)
             }
+            AuctionInstruction::AcceptAdmin {} => {
+                msg!("Instruction: AcceptAdmin");
+                Self::process_accept_admin(accounts, program_id)
+            }
+            AuctionInstruction::CreateAuctionHouse {
+                fee_bps,
+                referral_bps,
+                referral_from_fee,
+            } => {
+                msg!("Instruction: CreateAuctionHouse");
+                Self::process_create_auction_house(
+                    accounts,
+                    fee_bps,
+                    referral_bps,
+                    referral_from_fee,
+                    program_id,
+                )
+            }
+            AuctionInstruction::UpdateAuctionHouse {
+                fee_bps,
+                referral_bps,
+                referral_from_fee,
+            } => {
+                msg!("Instruction: UpdateAuctionHouse");
+                Self::process_update_auction_house(
+                    accounts,
+                    fee_bps,
+                    referral_bps,
+                    referral_from_fee,
+                    program_id,
+                )
+            }
+            AuctionInstruction::SetHouseCurrency { allowed } => {
+                msg!("Instruction: SetHouseCurrency");
+                Self::process_set_house_currency(accounts, allowed, program_id)
+            }
+        }
+    }
+
+    /// Checks the program is not paused with the program config account given last, and returns the rest of the accounts
+    /// Only exhibiting, bidding and buying are paused, so that the tokens already deposited can always be taken out.
+    fn check_not_paused<'a, 'b>(
+        accounts: &'a [AccountInfo<'b>],
+        program_id: &Pubkey,
+    ) -> Result<&'a [AccountInfo<'b>], ProgramError> {
+        let (accounts, config) = split_program_config(program_id, accounts)?;
+        if config.is_some_and(|config| config.paused) {
+            return Err(AuctionError::ProgramPaused.into());
         }
+        Ok(accounts)
     }
 
     fn process_exhibit(
         accounts: &[AccountInfo],
-        initial_price: u64,
-        auction_duration_sec: u64,
+        args: ExhibitArgs,
         program_id: &Pubkey,
     ) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let exhibitor_account = next_account_info(account_info_iter)?;
-
-        // Make sure the seller is signed with the client's private key
-        // In the blockchain, the public key is known by solscan etc., so it is necessary to confirm that the call is correctly signed to prevent spoofing.
-        if !exhibitor_account.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-
-        let exhibitor_nft_account = next_account_info(account_info_iter)?;
-        let exhibitor_nft_temp_account = next_account_info(account_info_iter)?;
-        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        let ExhibitArgs {
+            initial_price,
+            seconds: auction_duration_sec,
+            reserve_price,
+            reserve_price_hash,
+            min_increment,
+            min_increment_bps,
+            extension_window,
+            extension_seconds,
+            max_extension_seconds,
+            start_at,
+            buy_now_price,
+            kind,
+            floor_price,
+            decay_curve,
+            decay_interval,
+            decay_rate_bps,
+            reveal_seconds,
+            forfeit_unrevealed,
+            quantity,
+            native,
+        } = args;
 
         // 'escrow_account' here is the organizer of the auction.
         // Generally, 'Escrow' is an account for exchanging tokens to prevent them from being taken away.
         // When exchanging tokens between two parties, the one who sent it first without the intervention of 'Escrow' runs the risk of being taken away.
         // - reference: https://paulx.dev/blog/2021/01/14/programming-on-solana-an-introduction/
         // This time, we will prevent fraud by saving information such as listing NFT, bid amount, deadline, etc. in escrow_account.
-        let escrow_account = next_account_info(account_info_iter)?;
-        let sys_var_rent_account = next_account_info(account_info_iter)?;
+        let ExhibitAccounts {
+            exhibitor_account,
+            exhibitor_nft_account,
+            exhibitor_nft_temp_account,
+            exhibitor_ft_receiving_account,
+            ft_mint_account,
+            escrow_account,
+            rent,
+            clock,
+            token_program,
+            system_program,
+            nft_mint,
+            escrow_bump_seed,
+            bid_book,
+            vault,
+            fee_config,
+            royalty,
+            auction_house,
+            bundle,
+        } = ExhibitAccounts::parse(program_id, accounts, kind, native)?;
 
-        // In Solana, rent will be charged according to the amount of data stored in the blockchain.
-        // If payment is made for each epoch and the balance becomes zero, the data in the account will be lost.
-        // By keeping the rent equivalent to 2 years from the beginning, you will be exempt from paying the rent.
-        // 'is_exempt' checks if there is a minimum balance that will be exempt from payment.
-        // This check is required as it is not good for data to disappear during the auction period.
-        // By the way, the amount of data held by this auction system is 209 bytes. Therefore, the required rent will be 0.00234552 SOL.
-        let rent = &Rent::from_account_info(sys_var_rent_account)?;
-        if !rent.is_exempt(escrow_account.lamports(), escrow_account.data_len()) {
-            return Err(AuctionError::NotRentExempt.into());
-        }
+        // The escrow account is a PDA derived from the auction house, the exhibitor and the exhibiting NFT's mint, and is created by this program.
+        // Since the address is determined only by them, the client can find the auction without any index,
+        // and the same NFT can't be exhibited twice while the auction is live because the escrow account already exists.
+        Self::create_pda_account(
+            exhibitor_account,
+            escrow_account,
+            system_program,
+            &rent,
+            &[
+                ESCROW_SEED,
+                auction_house.as_ref(),
+                exhibitor_account.key.as_ref(),
+                nft_mint.as_ref(),
+                &[escrow_bump_seed],
+            ],
+            Auction::LEN,
+            program_id,
+        )?;
 
         // I am extracting auction data from the blockchain.
         // Since there is nothing at first, it contains an empty value obtained from fixed-length data.
@@ -96,25 +260,217 @@ impl Processor {
             return Err(ProgramError::AccountAlreadyInitialized);
         }
 
-        let sys_var_clock_account = next_account_info(account_info_iter)?;
-        let clock = &Clock::from_account_info(sys_var_clock_account)?;
-
         // Create auction data
         auction_info.is_initialized = true;
         auction_info.exhibitor_pubkey = *exhibitor_account.key;
+        auction_info.exhibitor_nft_pubkey = *exhibitor_nft_account.key;
         auction_info.exhibiting_nft_temp_pubkey = *exhibitor_nft_temp_account.key;
         auction_info.exhibitor_ft_receiving_pubkey = *exhibitor_ft_receiving_account.key;
+        // Bids are accepted only in the FT of the given mint, so that no one can outbid with a worthless token.
+        // The bids of the native SOL auction are paid in lamports, so there is no FT mint.
+        if !native {
+            auction_info.ft_mint_pubkey = *ft_mint_account.key;
+        }
+        auction_info.nft_mint_pubkey = nft_mint;
         auction_info.price = initial_price;
-        auction_info.end_at = clock.unix_timestamp.add(auction_duration_sec as i64);
-        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        // The reserve price is either visible or hidden, not both.
+        if reserve_price != 0 && reserve_price_hash != [0; 32] {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        auction_info.reserve_price = reserve_price;
+        auction_info.reserve_price_hash = reserve_price_hash;
+        // The buy-now price must be above the initial price, otherwise nobody would bid.
+        // It can't be combined with a hidden reserve, which couldn't be checked against it and would be bypassed by buying.
+        if buy_now_price != 0
+            && (buy_now_price <= initial_price
+                || buy_now_price < reserve_price
+                || reserve_price_hash != [0; 32])
+        {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        auction_info.buy_now_price = buy_now_price;
+        // The price of the Dutch auction decreases from the initial price to the floor price as time goes by,
+        // so the reserve price and the buy-now price of the English auction make no sense.
+        if kind == AuctionKind::Dutch {
+            if floor_price > initial_price
+                || reserve_price != 0
+                || reserve_price_hash != [0; 32]
+                || buy_now_price != 0
+            {
+                return Err(AuctionError::InvalidInstruction.into());
+            }
+            if decay_curve != DecayCurve::Linear && decay_interval == 0 {
+                return Err(AuctionError::InvalidInstruction.into());
+            }
+            if decay_curve == DecayCurve::Exponential
+                && (decay_rate_bps == 0 || decay_rate_bps > 10_000)
+            {
+                return Err(AuctionError::InvalidInstruction.into());
+            }
+        }
+        // The bids of the sealed bid auction are committed until the end and revealed in the following phase,
+        // so the price is unknown until then and the NFT can't be bought at the buy-now price.
+        if matches!(
+            kind,
+            AuctionKind::SealedFirstPrice | AuctionKind::SealedSecondPrice
+        ) {
+            if reveal_seconds == 0 || buy_now_price != 0 {
+                return Err(AuctionError::InvalidInstruction.into());
+            }
+            // The successful bidder of the second-price auction pays at least the initial price.
+            auction_info.second_price = initial_price;
+        }
+        auction_info.forfeit_unrevealed = forfeit_unrevealed;
+        // A lot of fungible or semi-fungible tokens of the same mint can be sold as one item.
+        if quantity == 0 {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        auction_info.quantity = quantity;
+        // Each of the top bidders of the uniform-price auction wins a unit of the lot at the same price,
+        // so the reserve price and the buy-now price make no sense.
+        if kind == AuctionKind::UniformPrice
+            && (quantity > MAX_UNIFORM_WINNERS as u64
+                || reserve_price != 0
+                || reserve_price_hash != [0; 32]
+                || buy_now_price != 0)
+        {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        // The buyer of the reverse auction escrows the budget up to the initial price instead of the NFT,
+        // and the sellers escrow the requested quantity of the token of the FT mint when they ask.
+        if kind == AuctionKind::Reverse
+            && (initial_price == 0
+                || reserve_price != 0
+                || reserve_price_hash != [0; 32]
+                || buy_now_price != 0
+                || !bundle.is_empty())
+        {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        // Only the auctions won by a single bidder paying their own price can be paid in lamports.
+        if native && !matches!(kind, AuctionKind::English | AuctionKind::Dutch) {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        // The wrapped SOL is unwrapped to the exhibitor's wallet only when the highest bidder pays it at settlement.
+        if !native
+            && *exhibitor_ft_receiving_account.owner == system_program::id()
+            && (kind != AuctionKind::English || buy_now_price != 0)
+        {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        // The fee is fixed at the time of exhibiting, so that changing the fee config doesn't affect the live auctions.
+        auction_info.auction_house_pubkey = auction_house;
+        auction_info.fee_bps = fee_config.fee_bps;
+        auction_info.treasury_pubkey = fee_config.treasury_pubkey;
+        auction_info.referral_bps = fee_config.referral_bps;
+        auction_info.referral_from_fee = fee_config.referral_from_fee;
+        // The royalties are fixed as well, and all of the fees must fit in the price, otherwise the auction could never be settled.
+        let referral_bps = if auction_info.referral_from_fee {
+            0
+        } else {
+            auction_info.referral_bps
+        };
+        if auction_info.fee_bps as u32
+            + referral_bps as u32
+            + royalty.seller_fee_basis_points as u32
+            > 10_000
+        {
+            return Err(AuctionError::FeesExceedPrice.into());
+        }
+        auction_info.royalty_bps = royalty.seller_fee_basis_points;
+        auction_info.creators_len = royalty.creators.len() as u8;
+        for ((creator_pubkey, creator_share), (creator, share)) in auction_info
+            .creator_pubkeys
+            .iter_mut()
+            .zip(auction_info.creator_shares.iter_mut())
+            .zip(royalty.creators)
+        {
+            *creator_pubkey = creator;
+            *creator_share = share;
+        }
+        auction_info.kind = kind;
+        auction_info.floor_price = floor_price;
+        auction_info.decay_curve = decay_curve;
+        auction_info.decay_interval = decay_interval;
+        auction_info.decay_rate_bps = decay_rate_bps;
+        auction_info.min_increment = min_increment;
+        auction_info.min_increment_bps = min_increment_bps;
+        // The auction can be listed ahead of a scheduled start, in which case the duration counts from the start.
+        auction_info.start_at = start_at.max(clock.unix_timestamp);
+        auction_info.end_at = i64::try_from(auction_duration_sec)
+            .ok()
+            .and_then(|seconds| auction_info.start_at.checked_add(seconds))
+            .ok_or(AuctionError::AmountOverflow)?;
+        // The reveal phase of the sealed bid auction follows the commit phase ending at the end.
+        if auction_info.is_sealed() {
+            auction_info.reveal_end_at = i64::try_from(reveal_seconds)
+                .ok()
+                .and_then(|seconds| auction_info.end_at.checked_add(seconds))
+                .ok_or(AuctionError::AmountOverflow)?;
+        }
+        // Late bids push the end forward so that bidders can't win by sniping at the last second.
+        auction_info.extension_window = extension_window;
+        auction_info.extension_seconds = extension_seconds;
+        if max_extension_seconds != 0 {
+            auction_info.max_end_at = i64::try_from(max_extension_seconds)
+                .ok()
+                .and_then(|seconds| auction_info.end_at.checked_add(seconds))
+                .ok_or(AuctionError::AmountOverflow)?;
+        }
 
         // 'PDA' is an account specific to this program
         //   - reference: https://solanacookbook.com/core-concepts/pdas.html#facts
-        // Here, the word 'escrow' is fixedly specified for seed, so no matter who calls it from anywhere, the PDA will be the same.
-        // You can sign by using bump_seed in the Solana Program.
+        // Here, the word 'escrow' and the escrow account's pubkey are specified for seed, so each auction gets its own PDA.
+        // If the PDA were shared by all auctions, a bug in one auction could move the tokens deposited in another one.
+        // You can sign by using bump_seed in the Solana Program, so it is saved in the auction data to be reused by the other instructions.
         // In this auction system, it is used to move the seller's NFT held by 'Escrow' to the winning bidder, and to move it with a PDA signature without the seller's signature.
-        let (pda, _bump_seed) = Pubkey::find_program_address(&[b"escrow"], program_id);
-        let token_program = next_account_info(account_info_iter)?;
+        let (pda, bump_seed) = Auction::find_authority_address(escrow_account.key, program_id);
+        auction_info.bump_seed = bump_seed;
+        // The lamports of the native SOL auction are held directly in the vault account owned by this program,
+        // so that they can be moved without any temporary token account.
+        if let Some((vault_account, vault_bump_seed)) = vault {
+            Self::create_pda_account(
+                exhibitor_account,
+                vault_account,
+                system_program,
+                &rent,
+                &[VAULT_SEED, escrow_account.key.as_ref(), &[vault_bump_seed]],
+                0,
+                program_id,
+            )?;
+            auction_info.vault_pubkey = *vault_account.key;
+        }
+        // The other NFTs in the lot are held in their own temporary accounts, and move together with the exhibiting NFT.
+        auction_info.bundle_len = bundle.len() as u8;
+        for (nft_temp_pubkey, item) in auction_info.bundle_nft_temp_pubkeys.iter_mut().zip(&bundle)
+        {
+            *nft_temp_pubkey = *item.nft_temp_account.key;
+        }
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+
+        // The top bids of the uniform-price auction are held in the bid book, which is too large to be held in the escrow account.
+        if let Some((bid_book_account, bid_book_bump_seed)) = bid_book {
+            Self::create_pda_account(
+                exhibitor_account,
+                bid_book_account,
+                system_program,
+                &rent,
+                &[
+                    BID_BOOK_SEED,
+                    escrow_account.key.as_ref(),
+                    &[bid_book_bump_seed],
+                ],
+                BidBook::LEN,
+                program_id,
+            )?;
+            let mut bid_book = BidBook::unpack_unchecked(&bid_book_account.try_borrow_data()?)?;
+            if bid_book.is_initialized() {
+                return Err(ProgramError::AccountAlreadyInitialized);
+            }
+            bid_book.is_initialized = true;
+            bid_book.escrow_pubkey = *escrow_account.key;
+            BidBook::pack(bid_book, &mut bid_book_account.try_borrow_mut_data()?)?;
+        }
 
         // Transfer NFT to escrow
         let exhibit_ix = spl_token::instruction::transfer(
@@ -123,7 +479,11 @@ impl Processor {
             exhibitor_nft_temp_account.key,
             exhibitor_account.key,
             &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
-            1,
+            if kind == AuctionKind::Reverse {
+                initial_price
+            } else {
+                quantity
+            },
         )?;
         msg!("Calling the token program to transfer NFT to PDA...");
         invoke(
@@ -153,200 +513,252 @@ impl Processor {
                 token_program.clone(),
             ],
         )?;
+
+        for BundleItemAccounts {
+            nft_temp_account,
+            nft_account,
+        } in bundle
+        {
+            Self::deposit_token(
+                token_program,
+                nft_account,
+                nft_temp_account,
+                exhibitor_account,
+                pda,
+                1,
+            )?;
+        }
         Ok(())
     }
 
     fn process_bid(accounts: &[AccountInfo], price: u64, program_id: &Pubkey) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let bidder_account = next_account_info(account_info_iter)?;
+        let BidAccounts {
+            bidder_account,
+            highest_bidder_account,
+            highest_bidder_ft_temp_account,
+            highest_bidder_ft_returning_account,
+            bidder_ft_temp_account,
+            bidder_ft_account,
+            bidder_nft_receiving_account,
+            escrow_account,
+            clock,
+            token_program,
+            pda_account,
+            mut auction_info,
+            bid_book,
+            system_program,
+            wsol,
+            referrer_account,
+        } = BidAccounts::parse(program_id, accounts)?;
 
-        // Make sure the bidder is signed with their private key
-        if !bidder_account.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
+        // The Dutch auction is won by buying, not bidding.
+        if !matches!(
+            auction_info.kind,
+            AuctionKind::English | AuctionKind::UniformPrice | AuctionKind::Reverse
+        ) {
+            return Err(AuctionError::InvalidAuctionKind.into());
+        }
+        if auction_info.start_at > clock.unix_timestamp {
+            return Err(AuctionError::NotStarted.into());
         }
-        let highest_bidder_account = next_account_info(account_info_iter)?;
-        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
-        let highest_bidder_ft_returning_account = next_account_info(account_info_iter)?;
-
-        let bidder_ft_temp_account = next_account_info(account_info_iter)?;
-        let bidder_ft_account = next_account_info(account_info_iter)?;
-
-        let escrow_account = next_account_info(account_info_iter)?;
-        let mut auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
-
-        let sys_var_clock_account = next_account_info(account_info_iter)?;
-        let clock = &Clock::from_account_info(sys_var_clock_account)?;
-
         if auction_info.end_at <= clock.unix_timestamp {
             return Err(AuctionError::InactiveAuction.into());
         }
 
-        // If it is not the highest bid, it will be an error
-        // It works as expected even if multiple bidders bid at the same time and any amount is specified.
-        // For example, if an instruction is issued that specifies 201 and 202 for the price of solana, the price that will be retained in the account will eventually be 202.
-        // The instruction can be either 201 and 202 succeed, or 202 succeeds and 201 fails.
-        if auction_info.price >= price {
-            return Err(AuctionError::InsufficientBidPrice.into());
+        if auction_info.kind == AuctionKind::Reverse {
+            Self::check_ask_price(&auction_info, price)?;
+        } else {
+            Self::check_bid_price(&auction_info, bid_book.as_ref().map(|(_, b)| b), price)?;
         }
 
-        // Check if the data held in the account matches the information passed by the client.
-        // The account used must be included in the Instruction argument 'accounts', as it is not possible to retrieve account information from within the Instruction using the account's public key.
-        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
-            return Err(AuctionError::InvalidInstruction.into());
-        }
-        if auction_info.highest_bidder_ft_returning_pubkey
-            != *highest_bidder_ft_returning_account.key
-        {
-            return Err(AuctionError::InvalidInstruction.into());
-        }
-        if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
-            return Err(AuctionError::InvalidInstruction.into());
-        }
-        if auction_info.highest_bidder_pubkey == *bidder_account.key {
+        let already_bid = match &bid_book {
+            Some((_, bid_book)) => bid_book.contains(bidder_account.key),
+            None => auction_info.highest_bidder_pubkey == *bidder_account.key,
+        };
+        if already_bid {
             return Err(AuctionError::AlreadyBid.into());
         }
-        let token_program = next_account_info(account_info_iter)?;
-        let pda_account = next_account_info(account_info_iter)?;
-        let (pda, bump_seed) = Pubkey::find_program_address(&[b"escrow"], program_id);
+
+        let bump_seed = [auction_info.bump_seed];
+        let signers_seeds: &[&[&[u8]]] =
+            &[&Auction::authority_seeds(escrow_account.key, &bump_seed)];
+        let pda = *pda_account.key;
 
         // Transfers the FT of the amount specified by 'price' from the bidder to escrow.
         // If you do not move the FT to 'Escrow' at the time of bidding,
         // you will not be able to settle due to insufficient FT at the end of the auction,
         // so this time we will move the FT to 'Escrow' at the time of bidding.
-        let transfer_to_escrow_ix = spl_token::instruction::transfer(
-            token_program.key,
-            bidder_ft_account.key,
-            bidder_ft_temp_account.key,
-            bidder_account.key,
-            &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
-            price,
-        )?;
-        msg!("Calling the token program to transfer FT to the escrow from the bidder");
-        invoke(
-            &transfer_to_escrow_ix,
-            &[
-                bidder_ft_account.clone(),
-                bidder_ft_temp_account.clone(),
-                bidder_account.clone(),
-                token_program.clone(),
-            ],
-        )?;
-
-        let owner_change_ix = spl_token::instruction::set_authority(
-            token_program.key,
-            bidder_ft_temp_account.key,
-            Some(&pda),
-            spl_token::instruction::AuthorityType::AccountOwner,
-            bidder_account.key,
-            &[], // owner_pubkey is default signer when the signer_pubkeys is empty.
-        )?;
-        msg!("Calling the token program to transfer token account ownership...");
-        invoke(
-            &owner_change_ix,
-            &[
-                bidder_ft_temp_account.clone(),
-                bidder_account.clone(),
-                token_program.clone(),
-            ],
-        )?;
+        // The seller of the reverse auction deposits the requested token instead, which is delivered to the buyer at settlement.
+        let deposit = if auction_info.kind == AuctionKind::Reverse {
+            auction_info.quantity
+        } else {
+            price
+        };
+        match (system_program, wsol) {
+            (Some(system_program), Some((native_mint_account, wsol_bump_seed))) => {
+                // The lamports are wrapped in the wSOL account created for the bidder instead of the temporary FT account.
+                Self::create_pda_account(
+                    bidder_account,
+                    bidder_ft_temp_account,
+                    system_program,
+                    &Rent::get()?,
+                    &[
+                        WSOL_SEED,
+                        escrow_account.key.as_ref(),
+                        bidder_account.key.as_ref(),
+                        &[wsol_bump_seed],
+                    ],
+                    TokenAccount::LEN,
+                    &spl_token::id(),
+                )?;
+                Self::wrap_lamports(
+                    token_program,
+                    bidder_account,
+                    bidder_ft_temp_account,
+                    native_mint_account,
+                    system_program,
+                    pda,
+                    deposit,
+                )?;
+            }
+            (Some(system_program), None) => {
+                Self::transfer_lamports(
+                    bidder_account,
+                    bidder_ft_temp_account,
+                    system_program,
+                    deposit,
+                )?;
+            }
+            _ => {
+                Self::deposit_token(
+                    token_program,
+                    bidder_ft_account,
+                    bidder_ft_temp_account,
+                    bidder_account,
+                    pda,
+                    deposit,
+                )?;
+            }
+        }
 
-        if auction_info.highest_bidder_pubkey != Pubkey::default(){
-            // Since the highest bidder has changed, we will return the FT that the highest bidder has deposited so far.
-            let transfer_to_previous_bidder_ix = spl_token::instruction::transfer(
-                token_program.key,
-                highest_bidder_ft_temp_account.key,
-                highest_bidder_ft_returning_account.key,
-                &pda,
-                &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
+        let has_outbid = match &bid_book {
+            Some((_, bid_book)) => bid_book.lowest_if_full(auction_info.quantity).is_some(),
+            None => auction_info.highest_bidder_pubkey != Pubkey::default(),
+        };
+        if has_outbid && auction_info.is_native() {
+            // The lamports deposited by the highest bidder so far are returned from the vault.
+            Self::withdraw_lamports(
+                highest_bidder_ft_temp_account,
+                highest_bidder_ft_returning_account,
                 auction_info.price,
             )?;
-            msg!("Calling the token program to transfer FT to the previous highest bidder from the escrow");
-            let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
-            invoke_signed(
-                &transfer_to_previous_bidder_ix,
-                &[
-                    highest_bidder_ft_temp_account.clone(),
-                    highest_bidder_ft_returning_account.clone(),
-                    pda_account.clone(),
-                    token_program.clone(),
-                ],
-                signers_seeds,
-            )?;
-
-            Self::close_temporary_ft(
+        } else if has_outbid {
+            // Since the highest bidder has changed, we will return the FT that the highest bidder has deposited so far.
+            Self::refund_bidder(
                 token_program,
                 highest_bidder_ft_temp_account,
+                highest_bidder_ft_returning_account,
                 highest_bidder_account,
-                pda,
                 pda_account,
                 signers_seeds,
             )?;
         }
 
+        // If the bid is placed within the extension window, the end is extended up to the limit.
+        if auction_info.end_at - clock.unix_timestamp <= auction_info.extension_window as i64 {
+            let mut end_at = auction_info
+                .end_at
+                .checked_add(auction_info.extension_seconds as i64)
+                .ok_or(AuctionError::AmountOverflow)?;
+            if auction_info.max_end_at != 0 {
+                end_at = end_at.min(auction_info.max_end_at);
+            }
+            if end_at > auction_info.end_at {
+                auction_info.end_at = end_at;
+                msg!("The auction end is extended to {}", end_at);
+            }
+        }
+
         // Save the auction data on solana chain
-        auction_info.price = price;
-        auction_info.highest_bidder_pubkey = *bidder_account.key;
-        auction_info.highest_bidder_ft_temp_pubkey = *bidder_ft_temp_account.key;
-        auction_info.highest_bidder_ft_returning_pubkey = *bidder_ft_account.key;
+        if let Some((bid_book_account, mut bid_book)) = bid_book {
+            if has_outbid {
+                bid_book.entries.pop();
+            }
+            bid_book.insert(BidEntry {
+                bidder_pubkey: *bidder_account.key,
+                bidder_ft_temp_pubkey: *bidder_ft_temp_account.key,
+                bidder_ft_returning_pubkey: *bidder_ft_account.key,
+                bidder_nft_receiving_pubkey: *bidder_nft_receiving_account.key,
+                price,
+            });
+            BidBook::pack(bid_book, &mut bid_book_account.try_borrow_mut_data()?)?;
+        } else {
+            auction_info.price = price;
+            auction_info.highest_bidder_pubkey = *bidder_account.key;
+            auction_info.highest_bidder_ft_temp_pubkey = *bidder_ft_temp_account.key;
+            auction_info.highest_bidder_ft_returning_pubkey = *bidder_ft_account.key;
+            auction_info.highest_bidder_nft_receiving_pubkey = *bidder_nft_receiving_account.key;
+            // The referrer goes with the highest bid, so the one of the outbid bidder is no longer paid.
+            auction_info.highest_bidder_referrer_pubkey =
+                referrer_account.map_or(Pubkey::default(), |account| *account.key);
+        }
         Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
         Ok(())
     }
 
     fn process_cancel(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let exhibitor_account = next_account_info(account_info_iter)?;
-
-        // Check if it is signed with the seller's private key
-        if !exhibitor_account.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-
-        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
-        let exhibiting_nft_returning_account = next_account_info(account_info_iter)?;
-        let escrow_account = next_account_info(account_info_iter)?;
-        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
-
-        // Allow only exhibitor to cancel
-        if auction_info.exhibitor_pubkey != *exhibitor_account.key {
-            return Err(ProgramError::InvalidAccountData);
-        }
-        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        let CancelAccounts {
+            exhibitor_account,
+            exhibiting_nft_temp_account,
+            exhibiting_nft_returning_account,
+            escrow_account,
+            token_program,
+            pda_account,
+            auction_info,
+            bid_book,
+            vault_account,
+            bundle,
+        } = CancelAccounts::parse(program_id, accounts)?;
 
         // Prevents cancellation if someone has already bid
-        if auction_info.highest_bidder_pubkey != Pubkey::default() {
+        if auction_info.highest_bidder_pubkey != Pubkey::default()
+            || auction_info.bid_count > 0
+            || bid_book
+                .as_ref()
+                .is_some_and(|(_, bid_book)| !bid_book.entries.is_empty())
+        {
             return Err(AuctionError::AlreadyBid.into());
         }
 
-        let (pda, bump_seed) = Pubkey::find_program_address(&[b"escrow"], program_id);
-        let token_program = next_account_info(account_info_iter)?;
-        let pda_account = next_account_info(account_info_iter)?;
-        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+        let bump_seed = [auction_info.bump_seed];
+        let signers_seeds: &[&[&[u8]]] =
+            &[&Auction::authority_seeds(escrow_account.key, &bump_seed)];
+        let pda = *pda_account.key;
 
         // The exhibitor will have the NFT returned.
-        let exhibiting_nft_temp_account_data =
-            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
-        let transfer_nft_to_exhibitor_ix = spl_token::instruction::transfer(
-            token_program.key,
-            exhibiting_nft_temp_account.key,
-            exhibiting_nft_returning_account.key,
-            &pda,
-            &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
-            exhibiting_nft_temp_account_data.amount,
+        Self::transfer_nft(
+            token_program,
+            exhibiting_nft_temp_account,
+            exhibiting_nft_returning_account,
+            pda_account,
+            signers_seeds,
         )?;
-        msg!("Calling the token program to transfer NFT to the exhibitor...");
-        invoke_signed(
-            &transfer_nft_to_exhibitor_ix,
-            &[
-                exhibiting_nft_temp_account.clone(),
-                exhibiting_nft_returning_account.clone(),
-                pda_account.clone(),
-                token_program.clone(),
-            ],
+        Self::transfer_bundle(
+            token_program,
+            &bundle,
+            exhibitor_account.key,
+            exhibitor_account,
+            pda_account,
             signers_seeds,
         )?;
 
+        if let Some((bid_book_account, _)) = bid_book {
+            Self::close_program_account(bid_book_account, exhibitor_account)?;
+        }
+        if let Some(vault_account) = vault_account {
+            Self::close_program_account(vault_account, exhibitor_account)?;
+        }
+
         // End the auction
         // If 'Escrow' has a token account with one or more quantities, close will fail.
         // In other words, all deposited FTs and NFTs must be returned to the owner.
@@ -361,121 +773,1856 @@ impl Processor {
         )
     }
 
-    fn process_close(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let highest_bidder_account = next_account_info(account_info_iter)?;
-
-        // The token account for receiving the winning bidder's NFT will be specified at the time of bidding,
-        // so make sure that it is signed with the winning bidder's private key.
-        if !highest_bidder_account.is_signer {
-            return Err(ProgramError::MissingRequiredSignature);
-        }
-
-        let exhibitor_account = next_account_info(account_info_iter)?;
-        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
-        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
-        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
-        let highest_bidder_nft_receiving_account = next_account_info(account_info_iter)?;
-        let escrow_account = next_account_info(account_info_iter)?;
-        let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
-
-        let sys_var_clock_account = next_account_info(account_info_iter)?;
-        let clock = &Clock::from_account_info(sys_var_clock_account)?;
+    fn process_reclaim(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let ReclaimAccounts {
+            exhibitor_account,
+            exhibiting_nft_temp_account,
+            exhibitor_nft_account,
+            escrow_account,
+            clock,
+            token_program,
+            pda_account,
+            auction_info,
+            bid_book,
+            vault_account,
+            bundle,
+        } = ReclaimAccounts::parse(program_id, accounts)?;
 
-        if auction_info.end_at > clock.unix_timestamp {
+        if auction_info.settle_at() > clock.unix_timestamp {
             msg!(
                 "Auction will be finished in {} seconds",
-                (auction_info.end_at - clock.unix_timestamp)
+                (auction_info.settle_at() - clock.unix_timestamp)
             );
             return Err(AuctionError::ActiveAuction.into());
         }
-        if auction_info.exhibitor_pubkey != *exhibitor_account.key {
-            return Err(ProgramError::InvalidAccountData);
-        }
-        if auction_info.exhibiting_nft_temp_pubkey != *exhibiting_nft_temp_account.key {
-            return Err(ProgramError::InvalidAccountData);
+
+        // Only the auction that nobody bid on can be reclaimed, otherwise it must be settled with the highest bidder.
+        if auction_info.highest_bidder_pubkey != Pubkey::default()
+            || bid_book
+                .as_ref()
+                .is_some_and(|(_, bid_book)| !bid_book.entries.is_empty())
+        {
+            return Err(AuctionError::NoBidderFound.into());
         }
-        if auction_info.exhibitor_ft_receiving_pubkey != *exhibitor_ft_receiving_account.key {
-            return Err(ProgramError::InvalidAccountData);
+
+        let bump_seed = [auction_info.bump_seed];
+        let signers_seeds: &[&[&[u8]]] =
+            &[&Auction::authority_seeds(escrow_account.key, &bump_seed)];
+        let pda = *pda_account.key;
+
+        // The NFT goes back to the account it was exhibited from.
+        Self::transfer_nft(
+            token_program,
+            exhibiting_nft_temp_account,
+            exhibitor_nft_account,
+            pda_account,
+            signers_seeds,
+        )?;
+        Self::transfer_bundle(
+            token_program,
+            &bundle,
+            exhibitor_account.key,
+            exhibitor_account,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        if let Some((bid_book_account, _)) = bid_book {
+            Self::close_program_account(bid_book_account, exhibitor_account)?;
         }
-        if auction_info.highest_bidder_ft_temp_pubkey != *highest_bidder_ft_temp_account.key {
-            return Err(ProgramError::InvalidAccountData);
+        if let Some(vault_account) = vault_account {
+            Self::close_program_account(vault_account, exhibitor_account)?;
         }
 
-        // Only the highest bidder can close auction.
-        if auction_info.highest_bidder_pubkey != *highest_bidder_account.key {
-            return Err(ProgramError::InvalidAccountData);
-        }
+        Self::close_escrow(
+            token_program,
+            exhibiting_nft_temp_account,
+            exhibitor_account,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
 
-        let (pda, bump_seed) = Pubkey::find_program_address(&[b"escrow"], program_id);
-        let token_program = next_account_info(account_info_iter)?;
-        let pda_account = next_account_info(account_info_iter)?;
-        let signers_seeds: &[&[&[u8]]] = &[&[&b"escrow"[..], &[bump_seed]]];
+    fn process_buy_now(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let BuyNowAccounts {
+            buy: accounts,
+            highest_bidder,
+        } = BuyNowAccounts::parse(program_id, accounts)?;
+        let auction_info = &accounts.auction_info;
 
-        let exhibiting_nft_temp_account_data =
-            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        // Once the bids reach the buy-now price, the NFT can be bought only by bidding.
+        if auction_info.buy_now_price == 0 || auction_info.price >= auction_info.buy_now_price {
+            return Err(AuctionError::BuyNowUnavailable.into());
+        }
+
+        let buy_now_price = auction_info.buy_now_price;
+        Self::sell(accounts, Some(highest_bidder), buy_now_price)
+    }
+
+    fn process_buy(accounts: &[AccountInfo], price: u64, program_id: &Pubkey) -> ProgramResult {
+        let accounts = BuyAccounts::parse(program_id, accounts)?;
+        let auction_info = &accounts.auction_info;
+
+        if auction_info.kind != AuctionKind::Dutch {
+            return Err(AuctionError::InvalidAuctionKind.into());
+        }
+
+        // The buyer specifies the maximum price, since the current price can change before the transaction is processed.
+        let current_price = Self::dutch_price(auction_info, &accounts.clock);
+        if price < current_price {
+            msg!("The current price is {}", current_price);
+            return Err(AuctionError::InsufficientBidPrice.into());
+        }
+
+        // The Dutch auction has no bidder to give back the deposit to.
+        Self::sell(accounts, None, current_price)
+    }
+
+    /// Sells the NFT to the buyer at the given price and ends the auction
+    /// The current highest bidder, if given and any, gets back the deposited FT
+    fn sell<'a, 'b>(
+        accounts: BuyAccounts<'a, 'b>,
+        highest_bidder: Option<HighestBidderAccounts<'a, 'b>>,
+        price: u64,
+    ) -> ProgramResult {
+        let BuyAccounts {
+            buyer_account,
+            buyer_ft_account,
+            buyer_nft_receiving_account,
+            exhibitor_account,
+            exhibitor_ft_receiving_account,
+            exhibiting_nft_temp_account,
+            escrow_account,
+            clock,
+            token_program,
+            pda_account,
+            auction_info,
+            system_program,
+            vault_account,
+            treasury_account,
+            royalty,
+            bundle,
+        } = accounts;
+
+        if auction_info.start_at > clock.unix_timestamp {
+            return Err(AuctionError::NotStarted.into());
+        }
+        if auction_info.end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+
+        let bump_seed = [auction_info.bump_seed];
+        let signers_seeds: &[&[&[u8]]] =
+            &[&Auction::authority_seeds(escrow_account.key, &bump_seed)];
+        let pda = *pda_account.key;
+
+        Self::check_lot(exhibiting_nft_temp_account, &auction_info)?;
+
+        // The buyer pays the exhibitor, the marketplace and the creators directly, so the FT doesn't need to be deposited in the escrow.
+        let (payments, proceeds) =
+            Self::split_proceeds(&auction_info, treasury_account, None, &royalty, price)?;
+        if let Some(system_program) = system_program {
+            Self::transfer_lamports(
+                buyer_account,
+                exhibitor_ft_receiving_account,
+                system_program,
+                proceeds,
+            )?;
+            // The fee and the royalties are paid in the wrapped SOL.
+            for (payee_account, amount) in payments {
+                Self::transfer_lamports(buyer_account, payee_account, system_program, amount)?;
+                Self::sync_native(token_program, payee_account)?;
+            }
+        } else {
+            Self::transfer_from_buyer(
+                token_program,
+                buyer_ft_account,
+                exhibitor_ft_receiving_account,
+                buyer_account,
+                proceeds,
+            )?;
+            for (payee_account, amount) in payments {
+                Self::transfer_from_buyer(
+                    token_program,
+                    buyer_ft_account,
+                    payee_account,
+                    buyer_account,
+                    amount,
+                )?;
+            }
+        }
+
+        let highest_bidder =
+            highest_bidder.filter(|_| auction_info.highest_bidder_pubkey != Pubkey::default());
+        if let Some(highest_bidder) = highest_bidder {
+            if let Some(vault_account) = vault_account {
+                Self::withdraw_lamports(
+                    vault_account,
+                    highest_bidder.highest_bidder_ft_returning_account,
+                    auction_info.price,
+                )?;
+            } else {
+                Self::refund_bidder(
+                    token_program,
+                    highest_bidder.highest_bidder_ft_temp_account,
+                    highest_bidder.highest_bidder_ft_returning_account,
+                    highest_bidder.highest_bidder_account,
+                    pda_account,
+                    signers_seeds,
+                )?;
+            }
+        }
+        if let Some(vault_account) = vault_account {
+            Self::close_program_account(vault_account, exhibitor_account)?;
+        }
+
+        Self::transfer_nft(
+            token_program,
+            exhibiting_nft_temp_account,
+            buyer_nft_receiving_account,
+            pda_account,
+            signers_seeds,
+        )?;
+        Self::transfer_bundle(
+            token_program,
+            &bundle,
+            buyer_account.key,
+            exhibitor_account,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        msg!("Sold at {}", price);
+        Self::close_escrow(
+            token_program,
+            exhibiting_nft_temp_account,
+            exhibitor_account,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_close(
+        accounts: &[AccountInfo],
+        reserve_reveal: Option<ReserveReveal>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        Self::settle(CloseAccounts::parse(program_id, accounts)?, reserve_reveal)
+    }
+
+    fn process_settle(
+        accounts: &[AccountInfo],
+        reserve_reveal: Option<ReserveReveal>,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        Self::settle(
+            CloseAccounts::parse_settle(program_id, accounts)?,
+            reserve_reveal,
+        )
+    }
+
+    fn settle(accounts: CloseAccounts, reserve_reveal: Option<ReserveReveal>) -> ProgramResult {
+        if accounts.auction_info.kind == AuctionKind::Reverse {
+            return Self::settle_reverse(accounts);
+        }
+
+        let CloseAccounts {
+            highest_bidder_account,
+            exhibitor_account,
+            exhibiting_nft_temp_account,
+            exhibitor_ft_receiving_account,
+            highest_bidder_ft_temp_account,
+            highest_bidder_nft_receiving_account,
+            escrow_account,
+            clock,
+            token_program,
+            pda_account,
+            highest_bidder_ft_returning_account,
+            exhibitor_nft_account,
+            winning_bid_account,
+            auction_info,
+            system_program,
+            treasury_account,
+            royalty,
+            referrer_account,
+            bundle,
+        } = accounts;
+
+        if auction_info.settle_at() > clock.unix_timestamp {
+            msg!(
+                "Auction will be finished in {} seconds",
+                (auction_info.settle_at() - clock.unix_timestamp)
+            );
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        let bump_seed = [auction_info.bump_seed];
+        let signers_seeds: &[&[&[u8]]] =
+            &[&Auction::authority_seeds(escrow_account.key, &bump_seed)];
+        let pda = *pda_account.key;
+
+        // If the highest price doesn't reach the reserve price, the sale is not completed.
+        // The bidder gets back the deposited FT and the exhibitor gets back the NFT.
+        let reserve_price = Self::reserve_price(&auction_info, reserve_reveal, &clock)?;
+        if reserve_price.is_none_or(|reserve_price| auction_info.price < reserve_price) {
+            msg!(
+                "The highest price {} doesn't reach the reserve price",
+                auction_info.price
+            );
+            if auction_info.is_native() {
+                Self::withdraw_lamports(
+                    highest_bidder_ft_temp_account,
+                    highest_bidder_ft_returning_account,
+                    auction_info.price,
+                )?;
+                Self::close_program_account(highest_bidder_ft_temp_account, exhibitor_account)?;
+            } else {
+                Self::refund_bidder(
+                    token_program,
+                    highest_bidder_ft_temp_account,
+                    highest_bidder_ft_returning_account,
+                    highest_bidder_account,
+                    pda_account,
+                    signers_seeds,
+                )?;
+            }
+            Self::transfer_nft(
+                token_program,
+                exhibiting_nft_temp_account,
+                exhibitor_nft_account,
+                pda_account,
+                signers_seeds,
+            )?;
+            Self::transfer_bundle(
+                token_program,
+                &bundle,
+                exhibitor_account.key,
+                exhibitor_account,
+                pda_account,
+                signers_seeds,
+            )?;
+            if let Some(winning_bid_account) = winning_bid_account {
+                Self::close_program_account(winning_bid_account, highest_bidder_account)?;
+            }
+            return Self::close_escrow(
+                token_program,
+                exhibiting_nft_temp_account,
+                exhibitor_account,
+                pda,
+                pda_account,
+                escrow_account,
+                signers_seeds,
+            );
+        }
+
+        let exhibiting_nft_temp_account_data =
+            Self::check_lot(exhibiting_nft_temp_account, &auction_info)?;
 
         // Transfer the winning NFT from Escrow to the winning bidder's token account
         let transfer_nft_to_highest_bidder_ix = spl_token::instruction::transfer(
             token_program.key,
-            exhibiting_nft_temp_account.key,
-            &highest_bidder_nft_receiving_account.key,
+            exhibiting_nft_temp_account.key,
+            highest_bidder_nft_receiving_account.key,
+            &pda,
+            &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
+            exhibiting_nft_temp_account_data.amount,
+        )?;
+        msg!("Calling the token program to transfer NFT to the highest bidder...");
+        invoke_signed(
+            &transfer_nft_to_highest_bidder_ix,
+            &[
+                exhibiting_nft_temp_account.clone(),
+                highest_bidder_nft_receiving_account.clone(),
+                pda_account.clone(),
+                token_program.clone(),
+            ],
+            signers_seeds,
+        )?;
+        Self::transfer_bundle(
+            token_program,
+            &bundle,
+            highest_bidder_account.key,
+            exhibitor_account,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        // The successful bidder of the second-price auction pays the second highest price, or the reserve price if it's higher.
+        let price = if auction_info.kind == AuctionKind::SealedSecondPrice {
+            auction_info
+                .second_price
+                .max(reserve_price.unwrap_or_default())
+        } else {
+            auction_info.price
+        };
+        msg!("The successful bidder pays {}", price);
+        let (payments, proceeds) = Self::split_proceeds(
+            &auction_info,
+            treasury_account,
+            referrer_account,
+            &royalty,
+            price,
+        )?;
+
+        // The lamports of the native SOL auction are paid from the vault, which is closed with the auction.
+        if auction_info.is_native() {
+            Self::withdraw_lamports(
+                highest_bidder_ft_temp_account,
+                exhibitor_ft_receiving_account,
+                proceeds,
+            )?;
+            // The fee and the royalties are paid in the wrapped SOL.
+            for (payee_account, amount) in payments {
+                Self::withdraw_lamports(highest_bidder_ft_temp_account, payee_account, amount)?;
+                Self::sync_native(token_program, payee_account)?;
+            }
+            Self::close_program_account(highest_bidder_ft_temp_account, exhibitor_account)?;
+        } else if let Some(system_program) = system_program {
+            Self::pay_from_escrow(
+                token_program,
+                highest_bidder_ft_temp_account,
+                payments,
+                pda_account,
+                signers_seeds,
+            )?;
+            // The wSOL account can be unwrapped only as a whole by closing it, so the lamports are gathered in the PDA once
+            // and the rest of them, including the rent, goes back to the bidder.
+            let lamports = highest_bidder_ft_temp_account.lamports();
+            Self::close_temporary_ft(
+                token_program,
+                highest_bidder_ft_temp_account,
+                pda_account,
+                pda,
+                pda_account,
+                signers_seeds,
+            )?;
+            Self::transfer_lamports_from_pda(
+                pda_account,
+                exhibitor_ft_receiving_account,
+                system_program,
+                proceeds,
+                signers_seeds,
+            )?;
+            Self::transfer_lamports_from_pda(
+                pda_account,
+                highest_bidder_account,
+                system_program,
+                lamports
+                    .checked_sub(proceeds)
+                    .ok_or(AuctionError::AmountOverflow)?,
+                signers_seeds,
+            )?;
+        } else {
+            Self::pay_from_escrow(
+                token_program,
+                highest_bidder_ft_temp_account,
+                payments,
+                pda_account,
+                signers_seeds,
+            )?;
+            // Transfer FT deposited in Escrow to exhibitor
+            // The deposit of the sealed bid can exceed the price, then the rest is returned to the bidder.
+            let transfer_ft_to_exhibitor_ix = spl_token::instruction::transfer(
+                token_program.key,
+                highest_bidder_ft_temp_account.key,
+                exhibitor_ft_receiving_account.key,
+                &pda,
+                &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
+                proceeds,
+            )?;
+            msg!("Calling the token program to transfer FT to the exhibitor...");
+            invoke_signed(
+                &transfer_ft_to_exhibitor_ix,
+                &[
+                    highest_bidder_ft_temp_account.clone(),
+                    exhibitor_ft_receiving_account.clone(),
+                    pda_account.clone(),
+                    token_program.clone(),
+                ],
+                signers_seeds,
+            )?;
+
+            Self::refund_bidder(
+                token_program,
+                highest_bidder_ft_temp_account,
+                highest_bidder_ft_returning_account,
+                highest_bidder_account,
+                pda_account,
+                signers_seeds,
+            )?;
+        }
+
+        if let Some(winning_bid_account) = winning_bid_account {
+            Self::close_program_account(winning_bid_account, highest_bidder_account)?;
+        }
+
+        Self::close_escrow(
+            token_program,
+            exhibiting_nft_temp_account,
+            exhibitor_account,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    /// Settles the reverse auction, the lowest ask is paid from the budget and the rest of the budget returns to the buyer
+    fn settle_reverse(accounts: CloseAccounts) -> ProgramResult {
+        let CloseAccounts {
+            highest_bidder_account,
+            exhibitor_account,
+            exhibiting_nft_temp_account,
+            exhibitor_ft_receiving_account,
+            highest_bidder_ft_temp_account,
+            highest_bidder_nft_receiving_account,
+            escrow_account,
+            clock,
+            token_program,
+            pda_account,
+            highest_bidder_ft_returning_account,
+            exhibitor_nft_account,
+            auction_info,
+            treasury_account,
+            royalty,
+            referrer_account,
+            ..
+        } = accounts;
+
+        if auction_info.settle_at() > clock.unix_timestamp {
+            msg!(
+                "Auction will be finished in {} seconds",
+                (auction_info.settle_at() - clock.unix_timestamp)
+            );
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        let bump_seed = [auction_info.bump_seed];
+        let signers_seeds: &[&[&[u8]]] =
+            &[&Auction::authority_seeds(escrow_account.key, &bump_seed)];
+        let pda = *pda_account.key;
+
+        // The seller is paid from the budget, so the fee is charged on the seller's proceeds.
+        msg!("The successful seller is paid {}", auction_info.price);
+        let (payments, proceeds) = Self::split_proceeds(
+            &auction_info,
+            treasury_account,
+            referrer_account,
+            &royalty,
+            auction_info.price,
+        )?;
+        Self::pay_from_escrow(
+            token_program,
+            exhibiting_nft_temp_account,
+            payments,
+            pda_account,
+            signers_seeds,
+        )?;
+        Self::transfer_from_escrow(
+            token_program,
+            exhibiting_nft_temp_account,
+            highest_bidder_nft_receiving_account,
+            pda_account,
+            proceeds,
+            signers_seeds,
+        )?;
+        Self::transfer_from_escrow(
+            token_program,
+            highest_bidder_ft_temp_account,
+            exhibitor_ft_receiving_account,
+            pda_account,
+            auction_info.quantity,
+            signers_seeds,
+        )?;
+        Self::refund_bidder(
+            token_program,
+            highest_bidder_ft_temp_account,
+            highest_bidder_ft_returning_account,
+            highest_bidder_account,
+            pda_account,
+            signers_seeds,
+        )?;
+        Self::transfer_nft(
+            token_program,
+            exhibiting_nft_temp_account,
+            exhibitor_nft_account,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        Self::close_escrow(
+            token_program,
+            exhibiting_nft_temp_account,
+            exhibitor_account,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_settle_uniform(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let SettleUniformAccounts {
+            exhibitor_account,
+            exhibiting_nft_temp_account,
+            exhibitor_ft_receiving_account,
+            exhibitor_nft_account,
+            escrow_account,
+            bid_book_account,
+            clock,
+            token_program,
+            pda_account,
+            treasury_account,
+            royalty,
+            bid_entries,
+            auction_info,
+            bid_book,
+        } = SettleUniformAccounts::parse(program_id, accounts)?;
+
+        if auction_info.settle_at() > clock.unix_timestamp {
+            msg!(
+                "Auction will be finished in {} seconds",
+                (auction_info.settle_at() - clock.unix_timestamp)
+            );
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        Self::check_lot(exhibiting_nft_temp_account, &auction_info)?;
+
+        let bump_seed = [auction_info.bump_seed];
+        let signers_seeds: &[&[&[u8]]] =
+            &[&Auction::authority_seeds(escrow_account.key, &bump_seed)];
+        let pda = *pda_account.key;
+
+        // Every winner pays the lowest winning price, even when some units are left unsold.
+        let clearing_price = bid_book.clearing_price(auction_info.price);
+        msg!("The clearing price is {}", clearing_price);
+        let (payments, proceeds) = Self::split_proceeds(
+            &auction_info,
+            treasury_account,
+            None,
+            &royalty,
+            clearing_price,
+        )?;
+
+        // Each winner gets a unit, and the difference between the bidding price and the clearing price is returned.
+        for BidEntryAccounts {
+            bidder_account,
+            bidder_ft_temp_account,
+            bidder_ft_returning_account,
+            bidder_nft_receiving_account,
+        } in bid_entries
+        {
+            Self::pay_from_escrow(
+                token_program,
+                bidder_ft_temp_account,
+                payments.clone(),
+                pda_account,
+                signers_seeds,
+            )?;
+            Self::transfer_from_escrow(
+                token_program,
+                bidder_ft_temp_account,
+                exhibitor_ft_receiving_account,
+                pda_account,
+                proceeds,
+                signers_seeds,
+            )?;
+            Self::transfer_from_escrow(
+                token_program,
+                exhibiting_nft_temp_account,
+                bidder_nft_receiving_account,
+                pda_account,
+                1,
+                signers_seeds,
+            )?;
+            Self::refund_bidder(
+                token_program,
+                bidder_ft_temp_account,
+                bidder_ft_returning_account,
+                bidder_account,
+                pda_account,
+                signers_seeds,
+            )?;
+        }
+
+        // The units not sold go back to the account they were exhibited from.
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        if exhibiting_nft_temp_account_data.amount > 0 {
+            Self::transfer_nft(
+                token_program,
+                exhibiting_nft_temp_account,
+                exhibitor_nft_account,
+                pda_account,
+                signers_seeds,
+            )?;
+        }
+
+        Self::close_program_account(bid_book_account, exhibitor_account)?;
+        Self::close_escrow(
+            token_program,
+            exhibiting_nft_temp_account,
+            exhibitor_account,
+            pda,
+            pda_account,
+            escrow_account,
+            signers_seeds,
+        )
+    }
+
+    fn process_set_fee_config(
+        accounts: &[AccountInfo],
+        fee_bps: u16,
+        referral_bps: u16,
+        referral_from_fee: bool,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let SetFeeConfigAccounts {
+            authority_account,
+            fee_config_account,
+            ft_mint_account,
+            treasury_account,
+            rent,
+            system_program,
+            fee_config_bump_seed,
+        } = SetFeeConfigAccounts::parse(program_id, accounts)?;
+
+        Self::check_fee_bps(fee_bps, referral_bps, referral_from_fee)?;
+
+        // The fee config account is created at the first time the fee of the mint is set.
+        if fee_config_account.owner != program_id {
+            Self::create_pda_account(
+                authority_account,
+                fee_config_account,
+                system_program,
+                &rent,
+                &[
+                    FEE_CONFIG_SEED,
+                    ft_mint_account.key.as_ref(),
+                    &[fee_config_bump_seed],
+                ],
+                FeeConfig::LEN,
+                program_id,
+            )?;
+        }
+
+        let mut fee_config = FeeConfig::unpack_unchecked(&fee_config_account.try_borrow_data()?)?;
+        fee_config.is_initialized = true;
+        fee_config.ft_mint_pubkey = *ft_mint_account.key;
+        fee_config.treasury_pubkey = *treasury_account.key;
+        fee_config.fee_bps = fee_bps;
+        fee_config.referral_bps = referral_bps;
+        fee_config.referral_from_fee = referral_from_fee;
+        FeeConfig::pack(fee_config, &mut fee_config_account.try_borrow_mut_data()?)?;
+
+        msg!(
+            "The marketplace fee of {} is {} bps and the referral fee is {} bps",
+            ft_mint_account.key,
+            fee_bps,
+            referral_bps
+        );
+        Ok(())
+    }
+
+    fn process_create_auction_house(
+        accounts: &[AccountInfo],
+        fee_bps: u16,
+        referral_bps: u16,
+        referral_from_fee: bool,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let CreateAuctionHouseAccounts {
+            admin_account,
+            authority_account,
+            house_account,
+            rent,
+            system_program,
+            house_bump_seed,
+        } = CreateAuctionHouseAccounts::parse(program_id, accounts)?;
+
+        Self::check_fee_bps(fee_bps, referral_bps, referral_from_fee)?;
+
+        Self::create_pda_account(
+            admin_account,
+            house_account,
+            system_program,
+            &rent,
+            &[
+                HOUSE_SEED,
+                authority_account.key.as_ref(),
+                &[house_bump_seed],
+            ],
+            AuctionHouse::LEN,
+            program_id,
+        )?;
+
+        let mut house = AuctionHouse::unpack_unchecked(&house_account.try_borrow_data()?)?;
+        if house.is_initialized() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        house.is_initialized = true;
+        house.authority_pubkey = *authority_account.key;
+        house.fee_bps = fee_bps;
+        house.referral_bps = referral_bps;
+        house.referral_from_fee = referral_from_fee;
+        house.bump_seed = house_bump_seed;
+        AuctionHouse::pack(house, &mut house_account.try_borrow_mut_data()?)?;
+
+        msg!(
+            "The auction house {} is created for {}",
+            house_account.key,
+            authority_account.key
+        );
+        Ok(())
+    }
+
+    fn process_update_auction_house(
+        accounts: &[AccountInfo],
+        fee_bps: u16,
+        referral_bps: u16,
+        referral_from_fee: bool,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let AuctionHouseAccounts {
+            house_account,
+            mut house,
+            ..
+        } = AuctionHouseAccounts::parse(program_id, accounts)?;
+
+        Self::check_fee_bps(fee_bps, referral_bps, referral_from_fee)?;
+
+        // The auctions already exhibited keep the fee copied at the time of exhibiting.
+        house.fee_bps = fee_bps;
+        house.referral_bps = referral_bps;
+        house.referral_from_fee = referral_from_fee;
+        AuctionHouse::pack(house, &mut house_account.try_borrow_mut_data()?)?;
+
+        msg!(
+            "The marketplace fee of the auction house {} is {} bps and the referral fee is {} bps",
+            house_account.key,
+            fee_bps,
+            referral_bps
+        );
+        Ok(())
+    }
+
+    fn process_set_house_currency(
+        accounts: &[AccountInfo],
+        allowed: bool,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let AuctionHouseAccounts {
+            house_account,
+            mut house,
+            currency,
+            ..
+        } = AuctionHouseAccounts::parse(program_id, accounts)?;
+        let (ft_mint_account, treasury_account) =
+            currency.ok_or(ProgramError::NotEnoughAccountKeys)?;
+
+        house
+            .currencies
+            .retain(|currency| currency.mint_pubkey != *ft_mint_account.key);
+        if allowed {
+            if house.currencies.len() >= MAX_HOUSE_CURRENCIES {
+                return Err(AuctionError::InvalidAuctionHouse.into());
+            }
+            house.currencies.push(HouseCurrency {
+                mint_pubkey: *ft_mint_account.key,
+                treasury_pubkey: *treasury_account.key,
+            });
+        }
+        AuctionHouse::pack(house, &mut house_account.try_borrow_mut_data()?)?;
+
+        msg!(
+            "{} is {} in the auction house {}",
+            ft_mint_account.key,
+            if allowed { "allowed" } else { "disallowed" },
+            house_account.key
+        );
+        Ok(())
+    }
+
+    /// Checks the marketplace fee and the referral fee in basis points
+    /// The referral fee paid out of the proceeds is added to the marketplace fee, so both of them can't exceed the whole.
+    fn check_fee_bps(fee_bps: u16, referral_bps: u16, referral_from_fee: bool) -> ProgramResult {
+        if fee_bps > 10_000
+            || referral_bps > 10_000
+            || (!referral_from_fee && fee_bps + referral_bps > 10_000)
+        {
+            return Err(AuctionError::InvalidInstruction.into());
+        }
+        Ok(())
+    }
+
+    fn process_initialize_config(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let InitializeConfigAccounts {
+            authority_account,
+            config_account,
+            rent,
+            system_program,
+            config_bump_seed,
+        } = InitializeConfigAccounts::parse(program_id, accounts)?;
+
+        Self::create_pda_account(
+            authority_account,
+            config_account,
+            system_program,
+            &rent,
+            &[CONFIG_SEED, &[config_bump_seed]],
+            ProgramConfig::LEN,
+            program_id,
+        )?;
+
+        let mut config = ProgramConfig::unpack_unchecked(&config_account.try_borrow_data()?)?;
+        if config.is_initialized() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+        config.is_initialized = true;
+        config.admin_pubkey = *authority_account.key;
+        ProgramConfig::pack(config, &mut config_account.try_borrow_mut_data()?)?;
+
+        msg!("The admin is {}", authority_account.key);
+        Ok(())
+    }
+
+    fn process_set_paused(
+        accounts: &[AccountInfo],
+        paused: bool,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let ProgramConfigAccounts {
+            signer_account,
+            config_account,
+            mut config,
+        } = ProgramConfigAccounts::parse(program_id, accounts)?;
+
+        if config.admin_pubkey != *signer_account.key {
+            return Err(AuctionError::InvalidAuthority.into());
+        }
+
+        config.paused = paused;
+        ProgramConfig::pack(config, &mut config_account.try_borrow_mut_data()?)?;
+
+        msg!(
+            "Exhibiting, bidding and buying are {}",
+            if paused { "paused" } else { "resumed" }
+        );
+        Ok(())
+    }
+
+    fn process_transfer_admin(
+        accounts: &[AccountInfo],
+        new_admin: Pubkey,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let ProgramConfigAccounts {
+            signer_account,
+            config_account,
+            mut config,
+        } = ProgramConfigAccounts::parse(program_id, accounts)?;
+
+        if config.admin_pubkey != *signer_account.key {
+            return Err(AuctionError::InvalidAuthority.into());
+        }
+
+        // The admin is not changed until the new one accepts, so that it can't be transferred to a wrong account.
+        config.pending_admin_pubkey = new_admin;
+        ProgramConfig::pack(config, &mut config_account.try_borrow_mut_data()?)?;
+
+        msg!("The admin is proposed to be transferred to {}", new_admin);
+        Ok(())
+    }
+
+    fn process_accept_admin(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let ProgramConfigAccounts {
+            signer_account,
+            config_account,
+            mut config,
+        } = ProgramConfigAccounts::parse(program_id, accounts)?;
+
+        if config.pending_admin_pubkey == Pubkey::default()
+            || config.pending_admin_pubkey != *signer_account.key
+        {
+            return Err(AuctionError::InvalidAuthority.into());
+        }
+
+        config.admin_pubkey = config.pending_admin_pubkey;
+        config.pending_admin_pubkey = Pubkey::default();
+        ProgramConfig::pack(config, &mut config_account.try_borrow_mut_data()?)?;
+
+        msg!("The admin is transferred to {}", signer_account.key);
+        Ok(())
+    }
+
+    fn process_commit_bid(
+        accounts: &[AccountInfo],
+        commitment: [u8; 32],
+        deposit: u64,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let CommitBidAccounts {
+            bidder_account,
+            bid_account,
+            bidder_ft_temp_account,
+            bidder_ft_account,
+            bidder_nft_receiving_account,
+            escrow_account,
+            rent,
+            clock,
+            token_program,
+            system_program,
+            pda_account,
+            mut auction_info,
+            bid_bump_seed,
+        } = CommitBidAccounts::parse(program_id, accounts)?;
+
+        if !auction_info.is_sealed() {
+            return Err(AuctionError::InvalidAuctionKind.into());
+        }
+        if auction_info.start_at > clock.unix_timestamp {
+            return Err(AuctionError::NotStarted.into());
+        }
+        if auction_info.end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+
+        // The sealed bid account can't be created twice, so each bidder can commit only once.
+        Self::create_pda_account(
+            bidder_account,
+            bid_account,
+            system_program,
+            &rent,
+            &[
+                BID_SEED,
+                escrow_account.key.as_ref(),
+                bidder_account.key.as_ref(),
+                &[bid_bump_seed],
+            ],
+            SealedBid::LEN,
+            program_id,
+        )?;
+        let mut bid_info = SealedBid::unpack_unchecked(&bid_account.try_borrow_data()?)?;
+        if bid_info.is_initialized() {
+            return Err(ProgramError::AccountAlreadyInitialized);
+        }
+
+        // Only the deposit is visible, so the bidder can deposit more than the price to hide it.
+        Self::deposit_token(
+            token_program,
+            bidder_ft_account,
+            bidder_ft_temp_account,
+            bidder_account,
+            *pda_account.key,
+            deposit,
+        )?;
+
+        bid_info.is_initialized = true;
+        bid_info.escrow_pubkey = *escrow_account.key;
+        bid_info.bidder_pubkey = *bidder_account.key;
+        bid_info.commitment = commitment;
+        bid_info.deposit = deposit;
+        bid_info.bidder_ft_temp_pubkey = *bidder_ft_temp_account.key;
+        bid_info.bidder_ft_returning_pubkey = *bidder_ft_account.key;
+        bid_info.bidder_nft_receiving_pubkey = *bidder_nft_receiving_account.key;
+        bid_info.exhibitor_ft_receiving_pubkey = auction_info.exhibitor_ft_receiving_pubkey;
+        bid_info.forfeit_unrevealed = auction_info.forfeit_unrevealed;
+        bid_info.bump_seed = auction_info.bump_seed;
+        SealedBid::pack(bid_info, &mut bid_account.try_borrow_mut_data()?)?;
+
+        auction_info.bid_count = auction_info
+            .bid_count
+            .checked_add(1)
+            .ok_or(AuctionError::AmountOverflow)?;
+        Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_reveal_bid(
+        accounts: &[AccountInfo],
+        price: u64,
+        salt: [u8; 32],
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        let RevealBidAccounts {
+            bidder_account,
+            bid_account,
+            escrow_account,
+            clock,
+            mut auction_info,
+            mut bid_info,
+        } = RevealBidAccounts::parse(program_id, accounts)?;
+
+        if auction_info.end_at > clock.unix_timestamp {
+            return Err(AuctionError::ActiveAuction.into());
+        }
+        if auction_info.reveal_end_at <= clock.unix_timestamp {
+            return Err(AuctionError::InactiveAuction.into());
+        }
+        if bid_info.is_revealed
+            || hashv(&[&price.to_le_bytes(), &salt]).to_bytes() != bid_info.commitment
+        {
+            return Err(AuctionError::InvalidBidReveal.into());
+        }
+        if price > bid_info.deposit {
+            return Err(AuctionError::InsufficientDeposit.into());
+        }
+
+        // The bid lower than the initial price or the highest revealed bid is still revealed to avoid the forfeiture, but it can't win.
+        // If the prices are the same, the bid revealed first wins.
+        let is_highest = if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            price >= auction_info.price
+        } else {
+            price > auction_info.price
+        };
+        // The top two revealed prices are tracked, since the successful bidder of the second-price auction pays the second one.
+        if is_highest {
+            msg!("The highest revealed price is {}", price);
+            if auction_info.highest_bidder_pubkey != Pubkey::default() {
+                auction_info.second_price = auction_info.price;
+            }
+            auction_info.price = price;
+            auction_info.highest_bidder_pubkey = *bidder_account.key;
+            auction_info.highest_bidder_ft_temp_pubkey = bid_info.bidder_ft_temp_pubkey;
+            auction_info.highest_bidder_ft_returning_pubkey = bid_info.bidder_ft_returning_pubkey;
+            auction_info.highest_bidder_nft_receiving_pubkey = bid_info.bidder_nft_receiving_pubkey;
+            Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        } else if price > auction_info.second_price {
+            auction_info.second_price = price;
+            Auction::pack(auction_info, &mut escrow_account.try_borrow_mut_data()?)?;
+        }
+
+        bid_info.is_revealed = true;
+        bid_info.price = price;
+        SealedBid::pack(bid_info, &mut bid_account.try_borrow_mut_data()?)?;
+        Ok(())
+    }
+
+    fn process_withdraw_bid(accounts: &[AccountInfo], program_id: &Pubkey) -> ProgramResult {
+        let WithdrawBidAccounts {
+            bidder_account,
+            bid_account,
+            bidder_ft_temp_account,
+            bidder_ft_returning_account,
+            exhibitor_ft_receiving_account,
+            clock,
+            token_program,
+            pda_account,
+            auction_info,
+            bid_info,
+        } = WithdrawBidAccounts::parse(program_id, accounts)?;
+
+        // While the auction is not closed, the deposits are locked until the reveal phase ends,
+        // and the deposit of the successful bidder is settled by closing the auction.
+        if let Some(auction_info) = auction_info {
+            if auction_info.settle_at() > clock.unix_timestamp {
+                return Err(AuctionError::ActiveAuction.into());
+            }
+            if auction_info.highest_bidder_pubkey == bid_info.bidder_pubkey {
+                return Err(AuctionError::BidNotWithdrawable.into());
+            }
+        }
+
+        let bump_seed = [bid_info.bump_seed];
+        let signers_seeds: &[&[&[u8]]] = &[&Auction::authority_seeds(
+            &bid_info.escrow_pubkey,
+            &bump_seed,
+        )];
+
+        // The deposit of the bid not revealed is sent to the exhibitor if the auction says so,
+        // otherwise anyone could bid with no intention of buying.
+        let ft_returning_account = if !bid_info.is_revealed && bid_info.forfeit_unrevealed {
+            msg!("The deposit is forfeited since the bid is not revealed");
+            exhibitor_ft_receiving_account
+        } else {
+            bidder_ft_returning_account
+        };
+        Self::refund_bidder(
+            token_program,
+            bidder_ft_temp_account,
+            ft_returning_account,
+            bidder_account,
+            pda_account,
+            signers_seeds,
+        )?;
+
+        msg!("Closing the sealed bid account...");
+        Self::close_program_account(bid_account, bidder_account)
+    }
+
+    /// Deposits the tokens of the given amount in the temporary account and transfers its ownership to the PDA
+    fn deposit_token<'a, 'b>(
+        token_program: &'a AccountInfo<'b>,
+        bidder_ft_account: &'a AccountInfo<'b>,
+        bidder_ft_temp_account: &'a AccountInfo<'b>,
+        bidder_account: &'a AccountInfo<'b>,
+        pda: Pubkey,
+        amount: u64,
+    ) -> ProgramResult {
+        let transfer_to_escrow_ix = spl_token::instruction::transfer(
+            token_program.key,
+            bidder_ft_account.key,
+            bidder_ft_temp_account.key,
+            bidder_account.key,
+            &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
+            amount,
+        )?;
+        msg!("Calling the token program to transfer FT to the escrow from the bidder");
+        invoke(
+            &transfer_to_escrow_ix,
+            &[
+                bidder_ft_account.clone(),
+                bidder_ft_temp_account.clone(),
+                bidder_account.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        let owner_change_ix = spl_token::instruction::set_authority(
+            token_program.key,
+            bidder_ft_temp_account.key,
+            Some(&pda),
+            spl_token::instruction::AuthorityType::AccountOwner,
+            bidder_account.key,
+            &[], // owner_pubkey is default signer when the signer_pubkeys is empty.
+        )?;
+        msg!("Calling the token program to transfer token account ownership...");
+        invoke(
+            &owner_change_ix,
+            &[
+                bidder_ft_temp_account.clone(),
+                bidder_account.clone(),
+                token_program.clone(),
+            ],
+        )
+    }
+
+    /// Transfers the tokens of the given amount from the signer's account
+    fn transfer_from_buyer<'a, 'b>(
+        token_program: &'a AccountInfo<'b>,
+        buyer_ft_account: &'a AccountInfo<'b>,
+        destination_account: &'a AccountInfo<'b>,
+        buyer_account: &'a AccountInfo<'b>,
+        amount: u64,
+    ) -> ProgramResult {
+        let transfer_ix = spl_token::instruction::transfer(
+            token_program.key,
+            buyer_ft_account.key,
+            destination_account.key,
+            buyer_account.key,
+            &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
+            amount,
+        )?;
+        msg!("Calling the token program to transfer FT from the buyer");
+        invoke(
+            &transfer_ix,
+            &[
+                buyer_ft_account.clone(),
+                destination_account.clone(),
+                buyer_account.clone(),
+                token_program.clone(),
+            ],
+        )
+    }
+
+    /// Splits the amount paid into the payments to the marketplace, the referrer and the creators, and the proceeds of the exhibitor
+    /// The fee and the royalties are charged on the whole amount, and each creator gets their share of the royalties.
+    /// The referral fee is either a share of the marketplace fee or charged on the whole amount as well.
+    fn split_proceeds<'a, 'b>(
+        auction_info: &Auction,
+        treasury_account: Option<&'a AccountInfo<'b>>,
+        referrer_account: Option<&'a AccountInfo<'b>>,
+        royalty: &RoyaltyAccounts<'a, 'b>,
+        amount: u64,
+    ) -> Result<(Payments<'a, 'b>, u64), ProgramError> {
+        let mut payments = Vec::new();
+        let mut fee = Self::basis_points(amount, auction_info.fee_bps)?;
+        let referral = match referrer_account {
+            Some(_) if auction_info.referral_from_fee => {
+                let referral = Self::basis_points(fee, auction_info.referral_bps)?;
+                fee = fee
+                    .checked_sub(referral)
+                    .ok_or(AuctionError::AmountOverflow)?;
+                referral
+            }
+            Some(_) => Self::basis_points(amount, auction_info.referral_bps)?,
+            None => 0,
+        };
+        msg!("The marketplace fee is {}", fee);
+        if let Some(treasury_account) = treasury_account.filter(|_| fee > 0) {
+            payments.push((treasury_account, fee));
+        }
+        if let Some(referrer_account) = referrer_account.filter(|_| referral > 0) {
+            msg!("The referrer receives {}", referral);
+            payments.push((referrer_account, referral));
+        }
+
+        let mut proceeds = payments
+            .iter()
+            .try_fold(amount, |proceeds, (_, payment)| {
+                proceeds.checked_sub(*payment)
+            })
+            .ok_or(AuctionError::AmountOverflow)?;
+
+        // The royalties never exceed what is left, so that the auction can always be settled.
+        let royalties = Self::basis_points(amount, royalty.seller_fee_basis_points)?;
+        for (creator_ft_account, share) in &royalty.creators {
+            let creator_royalty = royalties
+                .checked_mul(*share as u64)
+                .map(|value| value / 100)
+                .ok_or(AuctionError::AmountOverflow)?
+                .min(proceeds);
+            proceeds -= creator_royalty;
+            msg!(
+                "The creator's account {} receives {} of royalties",
+                creator_ft_account.key,
+                creator_royalty
+            );
+            if creator_royalty > 0 {
+                payments.push((*creator_ft_account, creator_royalty));
+            }
+        }
+
+        msg!("The exhibitor receives {}", proceeds);
+        Ok((payments, proceeds))
+    }
+
+    /// Calculates the given basis points of the amount
+    fn basis_points(amount: u64, bps: u16) -> Result<u64, ProgramError> {
+        (amount as u128)
+            .checked_mul(bps as u128)
+            .map(|value| value / 10_000)
+            .and_then(|value| u64::try_from(value).ok())
+            .ok_or_else(|| AuctionError::AmountOverflow.into())
+    }
+
+    /// Pays the fee and the royalties from the tokens held by the PDA
+    fn pay_from_escrow<'a, 'b>(
+        token_program: &'a AccountInfo<'b>,
+        source_account: &'a AccountInfo<'b>,
+        payments: Payments<'a, 'b>,
+        pda_account: &'a AccountInfo<'b>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        for (payee_account, amount) in payments {
+            Self::transfer_from_escrow(
+                token_program,
+                source_account,
+                payee_account,
+                pda_account,
+                amount,
+                signers_seeds,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Checks the bid outbids the current price by the minimum increment
+    fn check_bid_price(
+        auction_info: &Auction,
+        bid_book: Option<&BidBook>,
+        price: u64,
+    ) -> ProgramResult {
+        // The bid outbids the highest bid, or the lowest bid in the bid book of the uniform-price auction when it's full.
+        // While the bid book is not full, any bid from the initial price is accepted.
+        let outbid_price = match bid_book {
+            Some(bid_book) => bid_book
+                .lowest_if_full(auction_info.quantity)
+                .map(|entry| entry.price),
+            None => Some(auction_info.price),
+        };
+
+        // If it is not the highest bid, it will be an error
+        // It works as expected even if multiple bidders bid at the same time and any amount is specified.
+        // For example, if an instruction is issued that specifies 201 and 202 for the price of solana, the price that will be retained in the account will eventually be 202.
+        // The instruction can be either 201 and 202 succeed, or 202 succeeds and 201 fails.
+        // In addition, the bid must exceed the current price by the minimum increment, so that bidders aren't spammed with tiny-increment bids.
+        let min_price = match outbid_price {
+            Some(outbid_price) => Self::next_min_price(auction_info, outbid_price)?,
+            None => auction_info.price,
+        };
+        if price < min_price {
+            msg!("The bid price must be at least {}", min_price);
+            if outbid_price.unwrap_or(auction_info.price) >= price {
+                return Err(AuctionError::InsufficientBidPrice.into());
+            }
+            return Err(AuctionError::InsufficientBidIncrement.into());
+        }
+        Ok(())
+    }
+
+    /// Checks the ask of the reverse auction undercuts the current price by the minimum decrement
+    /// The first ask can be the ceiling price itself.
+    fn check_ask_price(auction_info: &Auction, price: u64) -> ProgramResult {
+        let max_price = if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            auction_info.price
+        } else {
+            let decrement =
+                Self::next_min_price(auction_info, auction_info.price)? - auction_info.price;
+            auction_info
+                .price
+                .checked_sub(decrement)
+                .ok_or(AuctionError::InsufficientBidIncrement)?
+        };
+        if price > max_price {
+            msg!("The ask price must be at most {}", max_price);
+            if price >= auction_info.price {
+                return Err(AuctionError::InsufficientBidPrice.into());
+            }
+            return Err(AuctionError::InsufficientBidIncrement.into());
+        }
+        Ok(())
+    }
+
+    fn next_min_price(auction_info: &Auction, current_price: u64) -> Result<u64, ProgramError> {
+        let increment_by_bps = (current_price as u128)
+            .checked_mul(auction_info.min_increment_bps as u128)
+            .map(|v| v / 10_000)
+            .and_then(|v| u64::try_from(v).ok())
+            .ok_or(AuctionError::AmountOverflow)?;
+        let increment = auction_info.min_increment.max(increment_by_bps).max(1);
+        Ok(current_price
+            .checked_add(increment)
+            .ok_or(AuctionError::AmountOverflow)?)
+    }
+
+    /// Checks the temporary NFT account still holds the full quantity of the lot before paying out
+    fn check_lot(
+        exhibiting_nft_temp_account: &AccountInfo,
+        auction_info: &Auction,
+    ) -> Result<TokenAccount, ProgramError> {
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        if exhibiting_nft_temp_account_data.amount < auction_info.quantity {
+            msg!(
+                "The escrow holds {} of {} tokens",
+                exhibiting_nft_temp_account_data.amount,
+                auction_info.quantity
+            );
+            return Err(AuctionError::LotQuantityMismatch.into());
+        }
+        Ok(exhibiting_nft_temp_account_data)
+    }
+
+    /// Current price of the Dutch auction, decreasing from the initial price to the floor price
+    fn dutch_price(auction_info: &Auction, clock: &Clock) -> u64 {
+        let start_price = auction_info.price as u128;
+        let floor_price = auction_info.floor_price as u128;
+        let elapsed = clock
+            .unix_timestamp
+            .saturating_sub(auction_info.start_at)
+            .max(0) as u128;
+        let duration = auction_info
+            .end_at
+            .saturating_sub(auction_info.start_at)
+            .max(1) as u128;
+        let interval = auction_info.decay_interval.max(1) as u128;
+
+        // All calculations are done in u128, so that they never overflow for any u64 price.
+        let price = match auction_info.decay_curve {
+            DecayCurve::Linear => {
+                start_price - (start_price - floor_price) * elapsed.min(duration) / duration
+            }
+            DecayCurve::Stepwise => {
+                let steps = (duration / interval).max(1);
+                start_price - (start_price - floor_price) * (elapsed / interval).min(steps) / steps
+            }
+            DecayCurve::Exponential => {
+                // The rate is raised to the power of the elapsed intervals by squaring, in fixed point of 18 decimals.
+                const ONE: u128 = 1_000_000_000_000_000_000;
+                let mut base =
+                    (10_000 - auction_info.decay_rate_bps.min(10_000) as u128) * ONE / 10_000;
+                let mut exp = elapsed / interval;
+                let mut factor = ONE;
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        factor = factor * base / ONE;
+                    }
+                    base = base * base / ONE;
+                    exp >>= 1;
+                }
+                (start_price * factor / ONE).max(floor_price)
+            }
+        };
+        price as u64
+    }
+
+    /// Reserve price of the auction, `None` if the hidden reserve price wasn't revealed in time
+    fn reserve_price(
+        auction_info: &Auction,
+        reserve_reveal: Option<ReserveReveal>,
+        clock: &Clock,
+    ) -> Result<Option<u64>, ProgramError> {
+        if auction_info.reserve_price_hash == [0; 32] {
+            return Ok(Some(auction_info.reserve_price));
+        }
+
+        // The hidden reserve price is committed as a hash at the time of exhibiting, so that bidders can't see it.
+        // It must be revealed with the same salt, otherwise anyone could claim any reserve price.
+        match reserve_reveal {
+            Some(ReserveReveal {
+                reserve_price,
+                salt,
+            }) => {
+                if hashv(&[&reserve_price.to_le_bytes(), &salt]).to_bytes()
+                    != auction_info.reserve_price_hash
+                {
+                    return Err(AuctionError::InvalidReserveReveal.into());
+                }
+                Ok(Some(reserve_price))
+            }
+            // The exhibitor could lock the bidder's FT forever by never revealing the reserve price,
+            // so it is treated as not met once the reveal period has passed.
+            None => {
+                if clock.unix_timestamp
+                    < auction_info
+                        .settle_at()
+                        .saturating_add(RESERVE_REVEAL_SECONDS)
+                {
+                    return Err(AuctionError::ReserveNotRevealed.into());
+                }
+                Ok(None)
+            }
+        }
+    }
+
+    fn refund_bidder<'a, 'b>(
+        token_program: &'a AccountInfo<'b>,
+        bidder_ft_temp_account: &'a AccountInfo<'b>,
+        bidder_ft_returning_account: &'a AccountInfo<'b>,
+        bidder_account: &'a AccountInfo<'b>,
+        pda_account: &'a AccountInfo<'b>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let bidder_ft_temp_account_data =
+            TokenAccount::unpack(&bidder_ft_temp_account.try_borrow_data()?)?;
+        // The wrapped SOL returned to a wallet is unwrapped by closing the account, which sends all its lamports there.
+        if bidder_ft_temp_account_data.is_native()
+            && *bidder_ft_returning_account.owner == system_program::id()
+        {
+            return Self::close_temporary_ft(
+                token_program,
+                bidder_ft_temp_account,
+                bidder_ft_returning_account,
+                *pda_account.key,
+                pda_account,
+                signers_seeds,
+            );
+        }
+        if bidder_ft_temp_account_data.amount > 0 {
+            let transfer_to_bidder_ix = spl_token::instruction::transfer(
+                token_program.key,
+                bidder_ft_temp_account.key,
+                bidder_ft_returning_account.key,
+                pda_account.key,
+                &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
+                bidder_ft_temp_account_data.amount,
+            )?;
+            msg!("Calling the token program to transfer FT to the bidder from the escrow");
+            invoke_signed(
+                &transfer_to_bidder_ix,
+                &[
+                    bidder_ft_temp_account.clone(),
+                    bidder_ft_returning_account.clone(),
+                    pda_account.clone(),
+                    token_program.clone(),
+                ],
+                signers_seeds,
+            )?;
+        }
+
+        Self::close_temporary_ft(
+            token_program,
+            bidder_ft_temp_account,
+            bidder_account,
+            *pda_account.key,
+            pda_account,
+            signers_seeds,
+        )
+    }
+
+    /// Creates the account of the given PDA owned by this program, paid by the payer
+    fn create_pda_account<'a, 'b>(
+        payer_account: &'a AccountInfo<'b>,
+        pda_account: &'a AccountInfo<'b>,
+        system_program: &'a AccountInfo<'b>,
+        rent: &Rent,
+        pda_seeds: &[&[u8]],
+        space: usize,
+        program_id: &Pubkey,
+    ) -> ProgramResult {
+        // In Solana, rent will be charged according to the amount of data stored in the blockchain.
+        // If payment is made for each epoch and the balance becomes zero, the data in the account will be lost.
+        // By keeping the rent equivalent to 2 years from the beginning, you will be exempt from paying the rent.
+        // The payer pays the minimum balance that will be exempt from payment, so the client doesn't need to know it.
+        let required_lamports = rent.minimum_balance(space);
+
+        // Since the address of the PDA is known in advance, anyone can send lamports to it.
+        // 'create_account' fails if the account already has lamports, so in that case only the shortage is transferred
+        // and the account is allocated and assigned to this program separately.
+        if pda_account.lamports() == 0 {
+            let create_account_ix = system_instruction::create_account(
+                payer_account.key,
+                pda_account.key,
+                required_lamports,
+                space as u64,
+                program_id,
+            );
+            msg!("Calling the system program to create the account...");
+            invoke_signed(
+                &create_account_ix,
+                &[
+                    payer_account.clone(),
+                    pda_account.clone(),
+                    system_program.clone(),
+                ],
+                &[pda_seeds],
+            )?;
+            return Ok(());
+        }
+
+        let shortage = required_lamports.saturating_sub(pda_account.lamports());
+        if shortage > 0 {
+            let transfer_ix =
+                system_instruction::transfer(payer_account.key, pda_account.key, shortage);
+            msg!("Calling the system program to fund the account...");
+            invoke(
+                &transfer_ix,
+                &[
+                    payer_account.clone(),
+                    pda_account.clone(),
+                    system_program.clone(),
+                ],
+            )?;
+        }
+        let allocate_ix = system_instruction::allocate(pda_account.key, space as u64);
+        let assign_ix = system_instruction::assign(pda_account.key, program_id);
+        msg!("Calling the system program to allocate and assign the account...");
+        invoke_signed(
+            &allocate_ix,
+            &[pda_account.clone(), system_program.clone()],
+            &[pda_seeds],
+        )?;
+        invoke_signed(
+            &assign_ix,
+            &[pda_account.clone(), system_program.clone()],
+            &[pda_seeds],
+        )
+    }
+
+    fn transfer_nft<'a, 'b>(
+        token_program: &'a AccountInfo<'b>,
+        exhibiting_nft_temp_account: &'a AccountInfo<'b>,
+        nft_receiving_account: &'a AccountInfo<'b>,
+        pda_account: &'a AccountInfo<'b>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let exhibiting_nft_temp_account_data =
+            TokenAccount::unpack(&exhibiting_nft_temp_account.try_borrow_data()?)?;
+        let transfer_nft_ix = spl_token::instruction::transfer(
+            token_program.key,
+            exhibiting_nft_temp_account.key,
+            nft_receiving_account.key,
+            pda_account.key,
+            &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
+            exhibiting_nft_temp_account_data.amount,
+        )?;
+        msg!("Calling the token program to transfer NFT from the escrow...");
+        invoke_signed(
+            &transfer_nft_ix,
+            &[
+                exhibiting_nft_temp_account.clone(),
+                nft_receiving_account.clone(),
+                pda_account.clone(),
+                token_program.clone(),
+            ],
+            signers_seeds,
+        )?;
+
+        Ok(())
+    }
+
+    /// Transfers the NFTs in the lot in addition to the exhibiting NFT to the recipient,
+    /// and closes their temporary accounts to return the rent to the exhibitor
+    fn transfer_bundle<'a, 'b>(
+        token_program: &'a AccountInfo<'b>,
+        bundle: &[BundleItemAccounts<'a, 'b>],
+        recipient: &Pubkey,
+        exhibitor_account: &'a AccountInfo<'b>,
+        pda_account: &'a AccountInfo<'b>,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        for BundleItemAccounts {
+            nft_temp_account,
+            nft_account,
+        } in bundle
+        {
+            if TokenAccount::unpack(&nft_account.try_borrow_data()?)?.owner != *recipient {
+                return Err(AuctionError::TokenAccountOwnerMismatch.into());
+            }
+            Self::transfer_nft(
+                token_program,
+                nft_temp_account,
+                nft_account,
+                pda_account,
+                signers_seeds,
+            )?;
+
+            let close_nft_temp_acc_ix = spl_token::instruction::close_account(
+                token_program.key,
+                nft_temp_account.key,
+                exhibitor_account.key,
+                pda_account.key,
+                &[], // owner_pubkey is default signer when the signer_pubkeys is empty.
+            )?;
+            msg!("Calling the token program to close the NFT temp account...");
+            invoke_signed(
+                &close_nft_temp_acc_ix,
+                &[
+                    (*nft_temp_account).clone(),
+                    exhibitor_account.clone(),
+                    pda_account.clone(),
+                    token_program.clone(),
+                ],
+                signers_seeds,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Transfers the lamports of the given amount from the signer with the system program
+    fn transfer_lamports<'a, 'b>(
+        from_account: &'a AccountInfo<'b>,
+        to_account: &'a AccountInfo<'b>,
+        system_program: &'a AccountInfo<'b>,
+        amount: u64,
+    ) -> ProgramResult {
+        let transfer_ix = system_instruction::transfer(from_account.key, to_account.key, amount);
+        msg!("Calling the system program to transfer lamports...");
+        invoke(
+            &transfer_ix,
+            &[
+                from_account.clone(),
+                to_account.clone(),
+                system_program.clone(),
+            ],
+        )
+    }
+
+    /// Wraps the lamports of the given amount into the wSOL account owned by the PDA
+    fn wrap_lamports<'a, 'b>(
+        token_program: &'a AccountInfo<'b>,
+        bidder_account: &'a AccountInfo<'b>,
+        wsol_account: &'a AccountInfo<'b>,
+        native_mint_account: &'a AccountInfo<'b>,
+        system_program: &'a AccountInfo<'b>,
+        pda: Pubkey,
+        amount: u64,
+    ) -> ProgramResult {
+        let initialize_ix = spl_token::instruction::initialize_account3(
+            token_program.key,
+            wsol_account.key,
+            native_mint_account.key,
             &pda,
-            &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
-            exhibiting_nft_temp_account_data.amount,
         )?;
-        msg!("Calling the token program to transfer NFT to the highest bidder...");
+        msg!("Calling the token program to initialize the wSOL account...");
+        invoke(
+            &initialize_ix,
+            &[
+                wsol_account.clone(),
+                native_mint_account.clone(),
+                token_program.clone(),
+            ],
+        )?;
+
+        Self::transfer_lamports(bidder_account, wsol_account, system_program, amount)?;
+        Self::sync_native(token_program, wsol_account)
+    }
+
+    /// Syncs the amount of the wSOL account with the lamports transferred to it
+    fn sync_native<'a, 'b>(
+        token_program: &'a AccountInfo<'b>,
+        wsol_account: &'a AccountInfo<'b>,
+    ) -> ProgramResult {
+        let sync_native_ix =
+            spl_token::instruction::sync_native(token_program.key, wsol_account.key)?;
+        msg!("Calling the token program to sync the wrapped SOL...");
+        invoke(
+            &sync_native_ix,
+            &[wsol_account.clone(), token_program.clone()],
+        )
+    }
+
+    /// Transfers the lamports of the given amount held by the PDA
+    fn transfer_lamports_from_pda<'a, 'b>(
+        pda_account: &'a AccountInfo<'b>,
+        destination: &'a AccountInfo<'b>,
+        system_program: &'a AccountInfo<'b>,
+        amount: u64,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let transfer_ix = system_instruction::transfer(pda_account.key, destination.key, amount);
+        msg!("Calling the system program to transfer lamports from the PDA...");
         invoke_signed(
-            &transfer_nft_to_highest_bidder_ix,
+            &transfer_ix,
             &[
-                exhibiting_nft_temp_account.clone(),
-                highest_bidder_nft_receiving_account.clone(),
                 pda_account.clone(),
-                token_program.clone(),
+                destination.clone(),
+                system_program.clone(),
             ],
             signers_seeds,
-        )?;
+        )
+    }
 
-        // Transfer FT deposited in Escrow to exhibitor
-        let highest_bidder_ft_temp_account_data =
-            TokenAccount::unpack(&highest_bidder_ft_temp_account.try_borrow_data()?)?;
-        let transfer_ft_to_exhibitor_ix = spl_token::instruction::transfer(
+    /// Withdraws the lamports of the given amount from the vault of the native SOL auction
+    /// The vault is owned by this program, so its lamports can be moved without the system program.
+    fn withdraw_lamports(
+        vault_account: &AccountInfo,
+        destination: &AccountInfo,
+        amount: u64,
+    ) -> ProgramResult {
+        **vault_account.try_borrow_mut_lamports()? = vault_account
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(AuctionError::AmountOverflow)?;
+        **destination.try_borrow_mut_lamports()? = destination
+            .lamports()
+            .checked_add(amount)
+            .ok_or(AuctionError::AmountOverflow)?;
+
+        Ok(())
+    }
+
+    /// Transfers the tokens of the given amount held by the PDA
+    fn transfer_from_escrow<'a, 'b>(
+        token_program: &'a AccountInfo<'b>,
+        source_account: &'a AccountInfo<'b>,
+        destination_account: &'a AccountInfo<'b>,
+        pda_account: &'a AccountInfo<'b>,
+        amount: u64,
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let transfer_ix = spl_token::instruction::transfer(
             token_program.key,
-            highest_bidder_ft_temp_account.key,
-            &exhibitor_ft_receiving_account.key,
-            &pda,
+            source_account.key,
+            destination_account.key,
+            pda_account.key,
             &[], // authority_pubkey is default signer when the signer_pubkeys is empty.
-            highest_bidder_ft_temp_account_data.amount,
+            amount,
         )?;
-        msg!("Calling the token program to transfer FT to the exhibitor...");
+        msg!("Calling the token program to transfer tokens from the escrow...");
         invoke_signed(
-            &transfer_ft_to_exhibitor_ix,
+            &transfer_ix,
             &[
-                highest_bidder_ft_temp_account.clone(),
-                exhibitor_ft_receiving_account.clone(),
+                source_account.clone(),
+                destination_account.clone(),
                 pda_account.clone(),
                 token_program.clone(),
             ],
             signers_seeds,
-        )?;
-
-        Self::close_temporary_ft(
-            token_program,
-            highest_bidder_ft_temp_account,
-            highest_bidder_account,
-            pda,
-            pda_account,
-            signers_seeds,
-        )?;
-
-        Self::close_escrow(
-            token_program,
-            exhibiting_nft_temp_account,
-            exhibitor_account,
-            pda,
-            pda_account,
-            escrow_account,
-            signers_seeds,
         )
     }
 
@@ -508,12 +2655,20 @@ impl Processor {
         )?;
 
         msg!("Closing the escrow account...");
-        **exhibitor_account.try_borrow_mut_lamports()? = exhibitor_account
+        Self::close_program_account(escrow_account, exhibitor_account)
+    }
+
+    /// Closes the account owned by this program and sends its rent to the destination
+    /// The data is zeroed and the account is given back to the system program first,
+    /// so that topping it up again in the same transaction can't revive the closed account.
+    fn close_program_account(account: &AccountInfo, destination: &AccountInfo) -> ProgramResult {
+        account.try_borrow_mut_data()?.fill(0);
+        account.assign(&system_program::id());
+        **destination.try_borrow_mut_lamports()? = destination
             .lamports()
-            .checked_add(escrow_account.lamports())
+            .checked_add(account.lamports())
             .ok_or(AuctionError::AmountOverflow)?;
-        **escrow_account.try_borrow_mut_lamports()? = 0;
-        *escrow_account.try_borrow_mut_data()? = &mut [];
+        **account.try_borrow_mut_lamports()? = 0;
 
         Ok(())
     }
@@ -548,3 +2703,1015 @@ impl Processor {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Royalty;
+    use solana_program::instruction::Instruction;
+    use solana_program::program_option::COption;
+    use spl_token::state::{AccountState, Mint};
+
+    /// Account data owned by the test, lent to the program as `AccountInfo`
+    /// The cross-program invocations are no-ops outside of the runtime, so the accounts created by them are allocated in advance.
+    struct TestAccount {
+        key: Pubkey,
+        is_signer: bool,
+        lamports: u64,
+        data: Vec<u8>,
+        owner: Pubkey,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, data: Vec<u8>, owner: Pubkey) -> Self {
+            TestAccount {
+                key,
+                is_signer: false,
+                lamports: 0,
+                data,
+                owner,
+            }
+        }
+
+        fn wallet() -> Self {
+            TestAccount {
+                is_signer: true,
+                ..Self::new(Pubkey::new_unique(), vec![], system_program::id())
+            }
+        }
+
+        fn program(key: Pubkey) -> Self {
+            Self::new(key, vec![], Pubkey::default())
+        }
+
+        fn token_account(owner: &Pubkey, mint: &Pubkey, amount: u64) -> Self {
+            let mut data = vec![0; TokenAccount::LEN];
+            let token_account = TokenAccount {
+                mint: *mint,
+                owner: *owner,
+                amount,
+                state: AccountState::Initialized,
+                ..TokenAccount::default()
+            };
+            TokenAccount::pack(token_account, &mut data).unwrap();
+            Self::new(Pubkey::new_unique(), data, spl_token::id())
+        }
+
+        fn mint() -> Self {
+            let mut data = vec![0; Mint::LEN];
+            let mint = Mint {
+                mint_authority: COption::None,
+                supply: 1,
+                decimals: 0,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            };
+            Mint::pack(mint, &mut data).unwrap();
+            Self::new(Pubkey::new_unique(), data, spl_token::id())
+        }
+
+        fn rent() -> Self {
+            let rent = Rent::default();
+            let mut data = rent.lamports_per_byte_year.to_le_bytes().to_vec();
+            data.extend_from_slice(&rent.exemption_threshold.to_le_bytes());
+            data.push(rent.burn_percent);
+            Self::new(solana_program::sysvar::rent::id(), data, Pubkey::default())
+        }
+
+        fn clock(unix_timestamp: i64) -> Self {
+            let mut data = vec![0; 32];
+            data.extend_from_slice(&unix_timestamp.to_le_bytes());
+            Self::new(solana_program::sysvar::clock::id(), data, Pubkey::default())
+        }
+
+        fn fee_config(program_id: &Pubkey, ft_mint: &Pubkey, fee_bps: u16) -> Self {
+            let mut data = vec![0; FeeConfig::LEN];
+            let fee_config = FeeConfig {
+                is_initialized: true,
+                ft_mint_pubkey: *ft_mint,
+                treasury_pubkey: Pubkey::new_unique(),
+                fee_bps,
+                referral_bps: 0,
+                referral_from_fee: false,
+            };
+            FeeConfig::pack(fee_config, &mut data).unwrap();
+            let (key, _) = FeeConfig::find_address(ft_mint, program_id);
+            Self::new(key, data, *program_id)
+        }
+
+        fn program_config(program_id: &Pubkey, paused: bool) -> Self {
+            let mut data = vec![0; ProgramConfig::LEN];
+            let config = ProgramConfig {
+                is_initialized: true,
+                admin_pubkey: Pubkey::new_unique(),
+                pending_admin_pubkey: Pubkey::default(),
+                paused,
+            };
+            ProgramConfig::pack(config, &mut data).unwrap();
+            let (key, _) = ProgramConfig::find_address(program_id);
+            Self::new(key, data, *program_id)
+        }
+    }
+
+    fn account_info(account: &mut TestAccount) -> AccountInfo<'_> {
+        let TestAccount {
+            key,
+            is_signer,
+            lamports,
+            data,
+            owner,
+        } = account;
+        AccountInfo::new(key, *is_signer, true, lamports, data, owner, false, 0)
+    }
+
+    fn process(
+        program_id: &Pubkey,
+        accounts: &mut [&mut TestAccount],
+        data: &[u8],
+    ) -> ProgramResult {
+        let accounts: Vec<AccountInfo> = accounts
+            .iter_mut()
+            .map(|account| {
+                let TestAccount {
+                    key,
+                    is_signer,
+                    lamports,
+                    data,
+                    owner,
+                } = &mut **account;
+                AccountInfo::new(key, *is_signer, true, lamports, data, owner, false, 0)
+            })
+            .collect();
+        Processor::process(program_id, &accounts, data)
+    }
+
+    #[test]
+    fn close_program_account_gives_back_zeroed_account() {
+        let program_id = Pubkey::new_unique();
+        let mut account = TestAccount::new(Pubkey::new_unique(), vec![1; Auction::LEN], program_id);
+        account.lamports = 100;
+        let mut destination = TestAccount::wallet();
+        destination.lamports = 5;
+
+        let (account_info, destination_info) =
+            (account_info(&mut account), account_info(&mut destination));
+        Processor::close_program_account(&account_info, &destination_info).unwrap();
+        assert_eq!(*account_info.owner, system_program::id());
+        drop((account_info, destination_info));
+
+        assert!(account.data.iter().all(|byte| *byte == 0));
+        assert_eq!(account.lamports, 0);
+        assert_eq!(destination.lamports, 105);
+    }
+
+    /// Dutch auction from 1000 down to 200 over 100 seconds
+    fn dutch_auction(decay_curve: DecayCurve, decay_interval: u64, decay_rate_bps: u16) -> Auction {
+        let mut auction_info = Auction::unpack_unchecked(&[0; Auction::LEN]).unwrap();
+        auction_info.kind = AuctionKind::Dutch;
+        auction_info.price = 1000;
+        auction_info.floor_price = 200;
+        auction_info.start_at = 0;
+        auction_info.end_at = 100;
+        auction_info.decay_curve = decay_curve;
+        auction_info.decay_interval = decay_interval;
+        auction_info.decay_rate_bps = decay_rate_bps;
+        auction_info
+    }
+
+    fn clock_at(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    #[test]
+    fn dutch_price_decreases_linearly_to_the_floor() {
+        let auction_info = dutch_auction(DecayCurve::Linear, 0, 0);
+        let prices: Vec<u64> = [-10, 0, 25, 50, 99, 100, 1000]
+            .iter()
+            .map(|at| Processor::dutch_price(&auction_info, &clock_at(*at)))
+            .collect();
+        assert_eq!(prices, [1000, 1000, 800, 600, 208, 200, 200]);
+    }
+
+    #[test]
+    fn dutch_price_decreases_stepwise_at_every_interval() {
+        // 3 steps of 30 seconds, the last one reaching the floor.
+        let auction_info = dutch_auction(DecayCurve::Stepwise, 30, 0);
+        let prices: Vec<u64> = [0, 29, 30, 60, 89, 90, 100]
+            .iter()
+            .map(|at| Processor::dutch_price(&auction_info, &clock_at(*at)))
+            .collect();
+        assert_eq!(prices, [1000, 1000, 734, 467, 467, 200, 200]);
+    }
+
+    #[test]
+    fn dutch_price_decreases_exponentially_down_to_the_floor() {
+        // 10% less at every 10 seconds, reaching the floor of 200 after 16 intervals.
+        let auction_info = dutch_auction(DecayCurve::Exponential, 10, 1000);
+        let prices: Vec<u64> = [0, 9, 10, 20, 30, 150, 160, 1000]
+            .iter()
+            .map(|at| Processor::dutch_price(&auction_info, &clock_at(*at)))
+            .collect();
+        assert_eq!(prices, [1000, 1000, 900, 810, 729, 205, 200, 200]);
+    }
+
+    #[test]
+    fn dutch_price_drops_to_the_floor_at_the_full_decay_rate() {
+        let auction_info = dutch_auction(DecayCurve::Exponential, 10, 10_000);
+        assert_eq!(Processor::dutch_price(&auction_info, &clock_at(9)), 1000);
+        assert_eq!(Processor::dutch_price(&auction_info, &clock_at(10)), 200);
+    }
+
+    #[test]
+    fn sealed_bid_is_revealed_after_the_commit_phase() {
+        let program_id = Pubkey::new_unique();
+        let now = 1_700_000_000;
+        let (seconds, reveal_seconds) = (3_600u64, 600u64);
+
+        let mut exhibitor = TestAccount::wallet();
+        let mut ft_mint = TestAccount::mint();
+        let nft_mint = Pubkey::new_unique();
+        let mut exhibitor_nft = TestAccount::token_account(&exhibitor.key, &nft_mint, 1);
+        let mut exhibitor_nft_temp = TestAccount::token_account(&exhibitor.key, &nft_mint, 0);
+        let mut exhibitor_ft = TestAccount::token_account(&exhibitor.key, &ft_mint.key, 0);
+        let (escrow, _) = Auction::find_escrow_address(
+            &Pubkey::default(),
+            &exhibitor.key,
+            &nft_mint,
+            &program_id,
+        );
+        let mut escrow = TestAccount::new(escrow, vec![0; Auction::LEN], program_id);
+        let mut rent = TestAccount::rent();
+        let mut token_program = TestAccount::program(spl_token::id());
+        let mut system_program = TestAccount::program(system_program::id());
+        let mut fee_config = TestAccount::fee_config(&program_id, &ft_mint.key, 250);
+        let (metadata, _) = Royalty::find_metadata_address(&nft_mint);
+        let mut metadata = TestAccount::new(metadata, vec![], system_program::id());
+
+        let mut args = vec![0u8; 145];
+        args[1..9].copy_from_slice(&100u64.to_le_bytes());
+        args[9..17].copy_from_slice(&seconds.to_le_bytes());
+        args[107] = AuctionKind::SealedFirstPrice as u8;
+        args[127..135].copy_from_slice(&reveal_seconds.to_le_bytes());
+        args[136..144].copy_from_slice(&1u64.to_le_bytes());
+        process(
+            &program_id,
+            &mut [
+                &mut exhibitor,
+                &mut exhibitor_nft,
+                &mut exhibitor_nft_temp,
+                &mut exhibitor_ft,
+                &mut ft_mint,
+                &mut escrow,
+                &mut rent,
+                &mut TestAccount::clock(now),
+                &mut token_program,
+                &mut system_program,
+                &mut fee_config,
+                &mut metadata,
+                &mut TestAccount::program_config(&program_id, false),
+            ],
+            &args,
+        )
+        .unwrap();
+        let auction_info = Auction::unpack(&escrow.data).unwrap();
+        assert_eq!(auction_info.fee_bps, 250);
+        assert_eq!(
+            auction_info.treasury_pubkey,
+            FeeConfig::unpack(&fee_config.data).unwrap().treasury_pubkey
+        );
+        assert_eq!(auction_info.end_at, now + seconds as i64);
+        assert_eq!(
+            auction_info.reveal_end_at,
+            now + (seconds + reveal_seconds) as i64
+        );
+
+        let mut bidder = TestAccount::wallet();
+        let (bid, _) = SealedBid::find_address(&escrow.key, &bidder.key, &program_id);
+        let mut bid = TestAccount::new(bid, vec![0; SealedBid::LEN], program_id);
+        let mut bidder_ft_temp = TestAccount::token_account(&bidder.key, &ft_mint.key, 0);
+        let mut bidder_ft = TestAccount::token_account(&bidder.key, &ft_mint.key, 500);
+        let mut bidder_nft = TestAccount::token_account(&bidder.key, &nft_mint, 0);
+        let (pda, _) = Auction::find_authority_address(&escrow.key, &program_id);
+        let mut pda = TestAccount::program(pda);
+        let (price, salt) = (300u64, [7u8; 32]);
+
+        let mut args = vec![8u8];
+        args.extend_from_slice(&hashv(&[&price.to_le_bytes(), &salt]).to_bytes());
+        args.extend_from_slice(&400u64.to_le_bytes());
+        process(
+            &program_id,
+            &mut [
+                &mut bidder,
+                &mut bid,
+                &mut bidder_ft_temp,
+                &mut bidder_ft,
+                &mut bidder_nft,
+                &mut escrow,
+                &mut rent,
+                &mut TestAccount::clock(now + 60),
+                &mut token_program,
+                &mut system_program,
+                &mut pda,
+                &mut TestAccount::program_config(&program_id, false),
+            ],
+            &args,
+        )
+        .unwrap();
+
+        let mut args = vec![9u8];
+        args.extend_from_slice(&price.to_le_bytes());
+        args.extend_from_slice(&salt);
+        process(
+            &program_id,
+            &mut [
+                &mut bidder,
+                &mut bid,
+                &mut escrow,
+                &mut TestAccount::clock(now + seconds as i64 + 60),
+            ],
+            &args,
+        )
+        .unwrap();
+        let auction_info = Auction::unpack(&escrow.data).unwrap();
+        assert_eq!(auction_info.price, price);
+        assert_eq!(auction_info.highest_bidder_pubkey, bidder.key);
+        assert!(SealedBid::unpack(&bid.data).unwrap().is_revealed);
+    }
+
+    /// Second-price auction at the initial price of 100, in the reveal phase until 1000
+    fn vickrey_escrow(program_id: &Pubkey) -> TestAccount {
+        let mut auction_info = Auction::unpack_unchecked(&[0; Auction::LEN]).unwrap();
+        auction_info.is_initialized = true;
+        auction_info.kind = AuctionKind::SealedSecondPrice;
+        auction_info.price = 100;
+        auction_info.second_price = 100;
+        auction_info.reveal_end_at = 1000;
+        let mut data = vec![0; Auction::LEN];
+        Auction::pack(auction_info, &mut data).unwrap();
+        TestAccount::new(Pubkey::new_unique(), data, *program_id)
+    }
+
+    /// Reveals a new bid of the given price on the auction and returns the bidder
+    fn reveal(program_id: &Pubkey, escrow: &mut TestAccount, price: u64) -> Pubkey {
+        let mut bidder = TestAccount::wallet();
+        let salt = [3u8; 32];
+        let mut bid_info = SealedBid::unpack_unchecked(&[0; SealedBid::LEN]).unwrap();
+        bid_info.is_initialized = true;
+        bid_info.escrow_pubkey = escrow.key;
+        bid_info.bidder_pubkey = bidder.key;
+        bid_info.commitment = hashv(&[&price.to_le_bytes(), &salt]).to_bytes();
+        bid_info.deposit = price;
+        let mut data = vec![0; SealedBid::LEN];
+        SealedBid::pack(bid_info, &mut data).unwrap();
+        let mut bid = TestAccount::new(Pubkey::new_unique(), data, *program_id);
+
+        let mut args = vec![9u8];
+        args.extend_from_slice(&price.to_le_bytes());
+        args.extend_from_slice(&salt);
+        process(
+            program_id,
+            &mut [&mut bidder, &mut bid, escrow, &mut TestAccount::clock(10)],
+            &args,
+        )
+        .unwrap();
+        bidder.key
+    }
+
+    #[test]
+    fn second_price_follows_the_reveal_order() {
+        let program_id = Pubkey::new_unique();
+        let mut escrow = vickrey_escrow(&program_id);
+
+        reveal(&program_id, &mut escrow, 300);
+        let winner = reveal(&program_id, &mut escrow, 500);
+        reveal(&program_id, &mut escrow, 400);
+        let auction_info = Auction::unpack(&escrow.data).unwrap();
+        assert_eq!(auction_info.highest_bidder_pubkey, winner);
+        assert_eq!((auction_info.price, auction_info.second_price), (500, 400));
+    }
+
+    #[test]
+    fn second_price_of_a_tie_is_the_winning_price() {
+        let program_id = Pubkey::new_unique();
+        let mut escrow = vickrey_escrow(&program_id);
+
+        // The bid revealed first wins the tie.
+        let winner = reveal(&program_id, &mut escrow, 300);
+        reveal(&program_id, &mut escrow, 300);
+        let auction_info = Auction::unpack(&escrow.data).unwrap();
+        assert_eq!(auction_info.highest_bidder_pubkey, winner);
+        assert_eq!((auction_info.price, auction_info.second_price), (300, 300));
+    }
+
+    #[test]
+    fn second_price_ignores_bids_below_the_initial_price() {
+        let program_id = Pubkey::new_unique();
+        let mut escrow = vickrey_escrow(&program_id);
+
+        reveal(&program_id, &mut escrow, 50);
+        let auction_info = Auction::unpack(&escrow.data).unwrap();
+        assert_eq!(auction_info.highest_bidder_pubkey, Pubkey::default());
+
+        // The single bid above the initial price pays the initial price.
+        let winner = reveal(&program_id, &mut escrow, 200);
+        let auction_info = Auction::unpack(&escrow.data).unwrap();
+        assert_eq!(auction_info.highest_bidder_pubkey, winner);
+        assert_eq!((auction_info.price, auction_info.second_price), (200, 100));
+
+        reveal(&program_id, &mut escrow, 150);
+        let auction_info = Auction::unpack(&escrow.data).unwrap();
+        assert_eq!(auction_info.highest_bidder_pubkey, winner);
+        assert_eq!((auction_info.price, auction_info.second_price), (200, 150));
+    }
+
+    /// Reverse auction with the ceiling price of 1000 and the minimum decrement of 1%
+    fn reverse_auction(lowest_ask: Option<u64>) -> Auction {
+        let mut auction_info = Auction::unpack_unchecked(&[0; Auction::LEN]).unwrap();
+        auction_info.kind = AuctionKind::Reverse;
+        auction_info.price = 1000;
+        auction_info.min_increment_bps = 100;
+        if let Some(lowest_ask) = lowest_ask {
+            auction_info.price = lowest_ask;
+            auction_info.highest_bidder_pubkey = Pubkey::new_unique();
+        }
+        auction_info
+    }
+
+    #[test]
+    fn first_ask_can_be_the_ceiling_price() {
+        let auction_info = reverse_auction(None);
+        assert!(Processor::check_ask_price(&auction_info, 1000).is_ok());
+        assert!(Processor::check_ask_price(&auction_info, 1).is_ok());
+        assert_eq!(
+            Processor::check_ask_price(&auction_info, 1001),
+            Err(AuctionError::InsufficientBidPrice.into())
+        );
+    }
+
+    #[test]
+    fn ask_must_undercut_the_lowest_ask_by_the_decrement() {
+        let auction_info = reverse_auction(Some(800));
+        assert!(Processor::check_ask_price(&auction_info, 792).is_ok());
+        assert_eq!(
+            Processor::check_ask_price(&auction_info, 793),
+            Err(AuctionError::InsufficientBidIncrement.into())
+        );
+        assert_eq!(
+            Processor::check_ask_price(&auction_info, 800),
+            Err(AuctionError::InsufficientBidPrice.into())
+        );
+    }
+
+    #[test]
+    fn ask_cannot_undercut_below_zero() {
+        let mut auction_info = reverse_auction(Some(5));
+        auction_info.min_increment = 10;
+        assert_eq!(
+            Processor::check_ask_price(&auction_info, 0),
+            Err(AuctionError::InsufficientBidIncrement.into())
+        );
+    }
+
+    #[test]
+    fn buy_now_gives_back_the_native_bid_from_the_vault_and_closes_it() {
+        let program_id = Pubkey::new_unique();
+        let nft_mint = Pubkey::new_unique();
+        let mut exhibitor = TestAccount::wallet();
+        exhibitor.is_signer = false;
+        let mut highest_bidder = TestAccount::wallet();
+        highest_bidder.is_signer = false;
+        let mut highest_bidder_returning =
+            TestAccount::new(highest_bidder.key, vec![], system_program::id());
+        let mut buyer = TestAccount::wallet();
+        let mut buyer_lamports = TestAccount::new(buyer.key, vec![], system_program::id());
+        let mut buyer_nft = TestAccount::token_account(&buyer.key, &nft_mint, 0);
+
+        let mut escrow = TestAccount::new(Pubkey::new_unique(), vec![0; Auction::LEN], program_id);
+        escrow.lamports = 50;
+        let (pda, bump_seed) = Auction::find_authority_address(&escrow.key, &program_id);
+        let mut pda = TestAccount::program(pda);
+        let mut nft_temp = TestAccount::token_account(&pda.key, &nft_mint, 1);
+        let (vault, _) = Auction::find_vault_address(&escrow.key, &program_id);
+        let mut vault = TestAccount::new(vault, vec![], program_id);
+        vault.lamports = 10 + 300;
+
+        let mut auction_info = Auction::unpack_unchecked(&[0; Auction::LEN]).unwrap();
+        auction_info.is_initialized = true;
+        auction_info.exhibitor_pubkey = exhibitor.key;
+        auction_info.exhibiting_nft_temp_pubkey = nft_temp.key;
+        auction_info.exhibitor_ft_receiving_pubkey = exhibitor.key;
+        auction_info.nft_mint_pubkey = nft_mint;
+        auction_info.ft_mint_pubkey = spl_token::native_mint::id();
+        auction_info.vault_pubkey = vault.key;
+        auction_info.bump_seed = bump_seed;
+        auction_info.price = 300;
+        auction_info.buy_now_price = 500;
+        auction_info.end_at = 1000;
+        auction_info.quantity = 1;
+        auction_info.highest_bidder_pubkey = highest_bidder.key;
+        auction_info.highest_bidder_ft_temp_pubkey = vault.key;
+        auction_info.highest_bidder_ft_returning_pubkey = highest_bidder.key;
+        Auction::pack(auction_info, &mut escrow.data).unwrap();
+        let mut highest_bidder_temp = TestAccount::new(vault.key, vec![], program_id);
+        let mut exhibitor_receiving = TestAccount::new(exhibitor.key, vec![], system_program::id());
+
+        process(
+            &program_id,
+            &mut [
+                &mut buyer,
+                &mut buyer_lamports,
+                &mut buyer_nft,
+                &mut exhibitor,
+                &mut exhibitor_receiving,
+                &mut nft_temp,
+                &mut highest_bidder,
+                &mut highest_bidder_temp,
+                &mut highest_bidder_returning,
+                &mut escrow,
+                &mut TestAccount::clock(500),
+                &mut TestAccount::program(spl_token::id()),
+                &mut pda,
+                &mut TestAccount::program(system_program::id()),
+                &mut vault,
+                &mut TestAccount::program_config(&program_id, false),
+            ],
+            &[6],
+        )
+        .unwrap();
+
+        // The bid goes back to the bidder, and the rent of the vault and the escrow to the exhibitor.
+        assert_eq!(highest_bidder_returning.lamports, 300);
+        assert_eq!(exhibitor.lamports, 10 + 50);
+        assert_eq!((vault.lamports, vault.owner), (0, system_program::id()));
+        assert_eq!((escrow.lamports, escrow.owner), (0, system_program::id()));
+    }
+
+    thread_local! {
+        static INVOKED: std::cell::RefCell<Vec<Instruction>> = const { std::cell::RefCell::new(Vec::new()) };
+    }
+
+    /// Records the cross-program invocations of the current test thread instead of dropping them
+    struct RecordingStubs;
+
+    impl solana_program::program_stubs::SyscallStubs for RecordingStubs {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            _account_infos: &[AccountInfo],
+            _signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            INVOKED.with(|invoked| invoked.borrow_mut().push(instruction.clone()));
+            Ok(())
+        }
+    }
+
+    fn record_invocations<F: FnOnce()>(f: F) -> Vec<Instruction> {
+        solana_program::program_stubs::set_syscall_stubs(Box::new(RecordingStubs));
+        INVOKED.with(|invoked| invoked.borrow_mut().clear());
+        f();
+        INVOKED.with(|invoked| invoked.take())
+    }
+
+    fn wsol_account(owner: &Pubkey, amount: u64) -> TestAccount {
+        let mut account = TestAccount::token_account(owner, &spl_token::native_mint::id(), amount);
+        let mut token_account = TokenAccount::unpack(&account.data).unwrap();
+        token_account.is_native = COption::Some(Rent::default().minimum_balance(TokenAccount::LEN));
+        TokenAccount::pack(token_account, &mut account.data).unwrap();
+        account
+    }
+
+    fn refund(
+        bidder_ft_temp: &mut TestAccount,
+        bidder_ft_returning: &mut TestAccount,
+        bidder: &mut TestAccount,
+        pda: &mut TestAccount,
+    ) -> Vec<Instruction> {
+        let mut token_program = TestAccount::program(spl_token::id());
+        let (token_program, bidder_ft_temp, bidder_ft_returning, bidder, pda) = (
+            account_info(&mut token_program),
+            account_info(bidder_ft_temp),
+            account_info(bidder_ft_returning),
+            account_info(bidder),
+            account_info(pda),
+        );
+        record_invocations(|| {
+            Processor::refund_bidder(
+                &token_program,
+                &bidder_ft_temp,
+                &bidder_ft_returning,
+                &bidder,
+                &pda,
+                &[],
+            )
+            .unwrap()
+        })
+    }
+
+    #[test]
+    fn refund_unwraps_native_bid_into_the_wallet() {
+        let mut pda = TestAccount::program(Pubkey::new_unique());
+        let mut bidder = TestAccount::wallet();
+        let mut bidder_ft_temp = wsol_account(&pda.key, 300);
+        let mut bidder_ft_returning = TestAccount::wallet();
+
+        let invoked = refund(
+            &mut bidder_ft_temp,
+            &mut bidder_ft_returning,
+            &mut bidder,
+            &mut pda,
+        );
+        let close_ix = spl_token::instruction::close_account(
+            &spl_token::id(),
+            &bidder_ft_temp.key,
+            &bidder_ft_returning.key,
+            &pda.key,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(invoked, vec![close_ix]);
+    }
+
+    #[test]
+    fn refund_keeps_native_bid_wrapped_for_a_token_account() {
+        let mut pda = TestAccount::program(Pubkey::new_unique());
+        let mut bidder = TestAccount::wallet();
+        let mut bidder_ft_temp = wsol_account(&pda.key, 300);
+        let mut bidder_ft_returning = wsol_account(&bidder.key, 0);
+
+        let invoked = refund(
+            &mut bidder_ft_temp,
+            &mut bidder_ft_returning,
+            &mut bidder,
+            &mut pda,
+        );
+        let transfer_ix = spl_token::instruction::transfer(
+            &spl_token::id(),
+            &bidder_ft_temp.key,
+            &bidder_ft_returning.key,
+            &pda.key,
+            &[],
+            300,
+        )
+        .unwrap();
+        let close_ix = spl_token::instruction::close_account(
+            &spl_token::id(),
+            &bidder_ft_temp.key,
+            &bidder.key,
+            &pda.key,
+            &[],
+        )
+        .unwrap();
+        assert_eq!(invoked, vec![transfer_ix, close_ix]);
+    }
+
+    #[test]
+    fn wrap_lamports_initializes_funds_and_syncs_the_wsol_account() {
+        let pda = Pubkey::new_unique();
+        let mut token_program = TestAccount::program(spl_token::id());
+        let mut bidder = TestAccount::wallet();
+        let mut wsol = TestAccount::new(
+            Pubkey::new_unique(),
+            vec![0; TokenAccount::LEN],
+            spl_token::id(),
+        );
+        let mut native_mint =
+            TestAccount::new(spl_token::native_mint::id(), vec![], spl_token::id());
+        let mut system = TestAccount::program(system_program::id());
+        let (token_program_info, bidder_info, wsol_info, native_mint_info, system_info) = (
+            account_info(&mut token_program),
+            account_info(&mut bidder),
+            account_info(&mut wsol),
+            account_info(&mut native_mint),
+            account_info(&mut system),
+        );
+
+        let invoked = record_invocations(|| {
+            Processor::wrap_lamports(
+                &token_program_info,
+                &bidder_info,
+                &wsol_info,
+                &native_mint_info,
+                &system_info,
+                pda,
+                300,
+            )
+            .unwrap()
+        });
+        let expected = vec![
+            spl_token::instruction::initialize_account3(
+                &spl_token::id(),
+                wsol_info.key,
+                &spl_token::native_mint::id(),
+                &pda,
+            )
+            .unwrap(),
+            system_instruction::transfer(bidder_info.key, wsol_info.key, 300),
+            spl_token::instruction::sync_native(&spl_token::id(), wsol_info.key).unwrap(),
+        ];
+        assert_eq!(invoked, expected);
+    }
+
+    #[test]
+    fn exhibit_is_rejected_in_a_currency_without_the_fee_config() {
+        let program_id = Pubkey::new_unique();
+        let mut exhibitor = TestAccount::wallet();
+        let mut ft_mint = TestAccount::mint();
+        let nft_mint = Pubkey::new_unique();
+        let mut exhibitor_nft = TestAccount::token_account(&exhibitor.key, &nft_mint, 1);
+        let mut exhibitor_nft_temp = TestAccount::token_account(&exhibitor.key, &nft_mint, 0);
+        let mut exhibitor_ft = TestAccount::token_account(&exhibitor.key, &ft_mint.key, 0);
+        let (escrow, _) = Auction::find_escrow_address(
+            &Pubkey::default(),
+            &exhibitor.key,
+            &nft_mint,
+            &program_id,
+        );
+        let mut escrow = TestAccount::new(escrow, vec![0; Auction::LEN], program_id);
+        let (fee_config, _) = FeeConfig::find_address(&ft_mint.key, &program_id);
+        let mut fee_config = TestAccount::new(fee_config, vec![], system_program::id());
+
+        let mut args = vec![0u8; 145];
+        args[1..9].copy_from_slice(&100u64.to_le_bytes());
+        args[9..17].copy_from_slice(&3_600u64.to_le_bytes());
+        args[136..144].copy_from_slice(&1u64.to_le_bytes());
+        let result = process(
+            &program_id,
+            &mut [
+                &mut exhibitor,
+                &mut exhibitor_nft,
+                &mut exhibitor_nft_temp,
+                &mut exhibitor_ft,
+                &mut ft_mint,
+                &mut escrow,
+                &mut TestAccount::rent(),
+                &mut TestAccount::clock(0),
+                &mut TestAccount::program(spl_token::id()),
+                &mut TestAccount::program(system_program::id()),
+                &mut fee_config,
+                &mut TestAccount::program_config(&program_id, false),
+            ],
+            &args,
+        );
+        assert_eq!(result, Err(AuctionError::InvalidFeeConfig.into()));
+    }
+
+    fn auction(fee_bps: u16, referral_bps: u16, referral_from_fee: bool) -> Auction {
+        let mut auction_info = Auction::unpack_unchecked(&[0; Auction::LEN]).unwrap();
+        auction_info.fee_bps = fee_bps;
+        auction_info.referral_bps = referral_bps;
+        auction_info.referral_from_fee = referral_from_fee;
+        auction_info
+    }
+
+    fn amounts(payments: &Payments) -> Vec<u64> {
+        payments.iter().map(|(_, amount)| *amount).collect()
+    }
+
+    #[test]
+    fn split_proceeds_leaves_rounding_dust_to_exhibitor() {
+        let (mut treasury, mut creator_a, mut creator_b) = (
+            TestAccount::wallet(),
+            TestAccount::wallet(),
+            TestAccount::wallet(),
+        );
+        let (treasury, creator_a, creator_b) = (
+            account_info(&mut treasury),
+            account_info(&mut creator_a),
+            account_info(&mut creator_b),
+        );
+        let royalty = RoyaltyAccounts {
+            seller_fee_basis_points: 500,
+            creators: vec![(&creator_a, 33), (&creator_b, 67)],
+        };
+
+        // The fee is 25.025 and the royalties are 50.05, of which the creators get 16.5 and 33.5.
+        let (payments, proceeds) = Processor::split_proceeds(
+            &auction(250, 0, false),
+            Some(&treasury),
+            None,
+            &royalty,
+            1_001,
+        )
+        .unwrap();
+        assert_eq!(amounts(&payments), vec![25, 16, 33]);
+        assert_eq!(proceeds, 927);
+        assert_eq!(amounts(&payments).iter().sum::<u64>() + proceeds, 1_001);
+    }
+
+    #[test]
+    fn split_proceeds_without_fee_pays_everything_to_exhibitor() {
+        let royalty = RoyaltyAccounts {
+            seller_fee_basis_points: 0,
+            creators: Vec::new(),
+        };
+        let (payments, proceeds) =
+            Processor::split_proceeds(&auction(0, 0, false), None, None, &royalty, 1_000).unwrap();
+        assert!(payments.is_empty());
+        assert_eq!(proceeds, 1_000);
+    }
+
+    #[test]
+    fn split_proceeds_does_not_overflow_at_the_maximum_price() {
+        let mut treasury = TestAccount::wallet();
+        let treasury = account_info(&mut treasury);
+        let royalty = RoyaltyAccounts {
+            seller_fee_basis_points: 0,
+            creators: Vec::new(),
+        };
+        let (payments, proceeds) = Processor::split_proceeds(
+            &auction(100, 0, false),
+            Some(&treasury),
+            None,
+            &royalty,
+            u64::MAX,
+        )
+        .unwrap();
+        assert_eq!(amounts(&payments), vec![u64::MAX / 100]);
+        assert_eq!(proceeds, u64::MAX - u64::MAX / 100);
+    }
+
+    #[test]
+    fn split_proceeds_pays_referral_out_of_fee_or_proceeds() {
+        let (mut treasury, mut referrer) = (TestAccount::wallet(), TestAccount::wallet());
+        let (treasury, referrer) = (account_info(&mut treasury), account_info(&mut referrer));
+        let royalty = RoyaltyAccounts {
+            seller_fee_basis_points: 0,
+            creators: Vec::new(),
+        };
+
+        let (payments, proceeds) = Processor::split_proceeds(
+            &auction(1_000, 2_000, true),
+            Some(&treasury),
+            Some(&referrer),
+            &royalty,
+            10_000,
+        )
+        .unwrap();
+        assert_eq!(amounts(&payments), vec![800, 200]);
+        assert_eq!(proceeds, 9_000);
+
+        let (payments, proceeds) = Processor::split_proceeds(
+            &auction(1_000, 2_000, false),
+            Some(&treasury),
+            Some(&referrer),
+            &royalty,
+            10_000,
+        )
+        .unwrap();
+        assert_eq!(amounts(&payments), vec![1_000, 2_000]);
+        assert_eq!(proceeds, 7_000);
+    }
+
+    #[test]
+    fn split_proceeds_clamps_royalties_to_what_is_left() {
+        let (mut treasury, mut creator) = (TestAccount::wallet(), TestAccount::wallet());
+        let (treasury, creator) = (account_info(&mut treasury), account_info(&mut creator));
+        let royalty = RoyaltyAccounts {
+            seller_fee_basis_points: 2_000,
+            creators: vec![(&creator, 100)],
+        };
+
+        let (payments, proceeds) = Processor::split_proceeds(
+            &auction(9_000, 0, false),
+            Some(&treasury),
+            None,
+            &royalty,
+            1_000,
+        )
+        .unwrap();
+        assert_eq!(amounts(&payments), vec![900, 100]);
+        assert_eq!(proceeds, 0);
+    }
+
+    #[test]
+    fn exhibiting_bidding_and_buying_are_rejected_while_paused() {
+        let program_id = Pubkey::new_unique();
+        let mut exhibit = vec![0u8; 145];
+        exhibit[136..144].copy_from_slice(&1u64.to_le_bytes());
+        let mut commit_bid = vec![8u8];
+        commit_bid.extend_from_slice(&[0; 40]);
+        let instructions = [
+            ("Exhibit", exhibit),
+            ("Bid", [&[1u8][..], &100u64.to_le_bytes()].concat()),
+            ("BuyNow", vec![6]),
+            ("Buy", [&[7u8][..], &100u64.to_le_bytes()].concat()),
+            ("CommitBid", commit_bid),
+        ];
+
+        for (name, data) in instructions {
+            // The config is checked before any other account, so the paused instruction fails even without them.
+            let result = process(
+                &program_id,
+                &mut [&mut TestAccount::program_config(&program_id, true)],
+                &data,
+            );
+            assert_eq!(result, Err(AuctionError::ProgramPaused.into()), "{}", name);
+
+            let result = process(
+                &program_id,
+                &mut [&mut TestAccount::program_config(&program_id, false)],
+                &data,
+            );
+            assert_eq!(result, Err(ProgramError::NotEnoughAccountKeys), "{}", name);
+        }
+    }
+
+    #[test]
+    fn cancelling_and_settling_are_not_paused() {
+        let program_id = Pubkey::new_unique();
+        for data in [vec![2u8], vec![4u8, 0], vec![10u8]] {
+            let result = process(
+                &program_id,
+                &mut [&mut TestAccount::program_config(&program_id, true)],
+                &data,
+            );
+            assert_ne!(result, Err(AuctionError::ProgramPaused.into()));
+        }
+    }
+
+    #[test]
+    fn exhibit_is_rejected_in_a_currency_not_allowed_in_the_house() {
+        let program_id = Pubkey::new_unique();
+        let mut exhibitor = TestAccount::wallet();
+        let mut ft_mint = TestAccount::mint();
+        let nft_mint = Pubkey::new_unique();
+        let mut exhibitor_nft = TestAccount::token_account(&exhibitor.key, &nft_mint, 1);
+        let mut exhibitor_nft_temp = TestAccount::token_account(&exhibitor.key, &nft_mint, 0);
+        let mut exhibitor_ft = TestAccount::token_account(&exhibitor.key, &ft_mint.key, 0);
+
+        let authority = Pubkey::new_unique();
+        let (house, bump_seed) = AuctionHouse::find_address(&authority, &program_id);
+        let mut data = vec![0; AuctionHouse::LEN];
+        let house_info = AuctionHouse {
+            is_initialized: true,
+            authority_pubkey: authority,
+            fee_bps: 100,
+            referral_bps: 0,
+            referral_from_fee: false,
+            bump_seed,
+            currencies: vec![HouseCurrency {
+                mint_pubkey: Pubkey::new_unique(),
+                treasury_pubkey: Pubkey::new_unique(),
+            }],
+        };
+        AuctionHouse::pack(house_info, &mut data).unwrap();
+        let mut house = TestAccount::new(house, data, program_id);
+        let (escrow, _) =
+            Auction::find_escrow_address(&house.key, &exhibitor.key, &nft_mint, &program_id);
+        let mut escrow = TestAccount::new(escrow, vec![0; Auction::LEN], program_id);
+
+        let mut args = vec![0u8; 145];
+        args[1..9].copy_from_slice(&100u64.to_le_bytes());
+        args[9..17].copy_from_slice(&3_600u64.to_le_bytes());
+        args[136..144].copy_from_slice(&1u64.to_le_bytes());
+        let result = process(
+            &program_id,
+            &mut [
+                &mut exhibitor,
+                &mut exhibitor_nft,
+                &mut exhibitor_nft_temp,
+                &mut exhibitor_ft,
+                &mut ft_mint,
+                &mut escrow,
+                &mut TestAccount::rent(),
+                &mut TestAccount::clock(0),
+                &mut TestAccount::program(spl_token::id()),
+                &mut TestAccount::program(system_program::id()),
+                &mut house,
+                &mut TestAccount::program_config(&program_id, false),
+            ],
+            &args,
+        );
+        assert_eq!(result, Err(AuctionError::CurrencyNotAllowed.into()));
+    }
+
+    #[test]
+    fn only_the_admin_can_create_an_auction_house() {
+        let program_id = Pubkey::new_unique();
+        let mut signer = TestAccount::wallet();
+        let authority = Pubkey::new_unique();
+        let (house, _) = AuctionHouse::find_address(&authority, &program_id);
+        let mut house = TestAccount::new(house, vec![], system_program::id());
+
+        let mut args = vec![17u8];
+        args.extend_from_slice(&100u16.to_le_bytes());
+        args.extend_from_slice(&0u16.to_le_bytes());
+        args.push(0);
+        let result = process(
+            &program_id,
+            &mut [
+                &mut signer,
+                &mut TestAccount::new(authority, vec![], system_program::id()),
+                &mut house,
+                &mut TestAccount::program_config(&program_id, false),
+                &mut TestAccount::rent(),
+                &mut TestAccount::program(system_program::id()),
+            ],
+            &args,
+        );
+        assert_eq!(result, Err(AuctionError::InvalidAuthority.into()));
+    }
+}
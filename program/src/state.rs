@@ -7,24 +7,283 @@ use solana_program::{
 use arrayref::{array_mut_ref, array_ref, array_refs, mut_array_refs};
 use solana_program::program_pack::IsInitialized;
 
+/// Seed prefix of the escrow account that holds the auction data
+pub const ESCROW_SEED: &[u8] = b"auction";
+/// Seed prefix of the PDA that owns the tokens deposited in the auction
+pub const AUTHORITY_SEED: &[u8] = b"escrow";
+/// Seed prefix of the sealed bid account of each bidder
+pub const BID_SEED: &[u8] = b"bid";
+/// Seed prefix of the vault account holding the lamports of the native SOL auction
+pub const VAULT_SEED: &[u8] = b"vault";
+/// Seed prefix of the wSOL account of each bidder, which wraps the lamports bid on the auction priced in wrapped SOL
+pub const WSOL_SEED: &[u8] = b"wsol";
+/// Seed prefix of the auction house of each authority
+pub const HOUSE_SEED: &[u8] = b"house";
+/// Seed of the program config holding the admin authority
+pub const CONFIG_SEED: &[u8] = b"config";
+/// Seed prefix of the fee config of each currency mint
+pub const FEE_CONFIG_SEED: &[u8] = b"fee";
+/// Seed prefix of the metadata account of the Metaplex Token Metadata program
+pub const METADATA_SEED: &[u8] = b"metadata";
+/// Program ID of the Metaplex Token Metadata program
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+/// Seed prefix of the bid book of the uniform-price auction
+pub const BID_BOOK_SEED: &[u8] = b"book";
+/// Maximum number of winners of the uniform-price auction, bounded so that all of them can be settled in a transaction
+pub const MAX_UNIFORM_WINNERS: usize = 5;
+/// Maximum number of the currencies allowed in an auction house, bounded so that the house account has a fixed size
+pub const MAX_HOUSE_CURRENCIES: usize = 4;
+/// Maximum number of the NFTs exhibited in a lot in addition to the exhibiting NFT, bounded so that all of them can be moved in a transaction
+pub const MAX_BUNDLE_ITEMS: usize = 4;
+/// Maximum number of the creators of an NFT allowed by the Metaplex Token Metadata program
+pub const MAX_CREATORS: usize = 5;
+/// Seconds after the auction end during which the settlement waits for the hidden reserve price to be revealed
+pub const RESERVE_REVEAL_SECONDS: i64 = 86_400;
+
+/// Kind of the auction, which determines how the price is decided
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AuctionKind {
+    /// Ascending price auction, the highest bidder wins when it ends
+    English,
+    /// Descending price auction, the first buyer wins at the current price
+    Dutch,
+    /// Sealed bid auction, the highest revealed bidder wins and pays their own price
+    SealedFirstPrice,
+    /// Sealed bid auction, the highest revealed bidder wins and pays the second highest price
+    SealedSecondPrice,
+    /// Ascending price auction of identical units, the top bidders win a unit each and pay the lowest winning price
+    UniformPrice,
+    /// Descending price procurement auction, the buyer escrows the budget and the seller with the lowest ask wins when it ends
+    Reverse,
+}
+
+impl TryFrom<u8> for AuctionKind {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(AuctionKind::English),
+            1 => Ok(AuctionKind::Dutch),
+            2 => Ok(AuctionKind::SealedFirstPrice),
+            3 => Ok(AuctionKind::SealedSecondPrice),
+            4 => Ok(AuctionKind::UniformPrice),
+            5 => Ok(AuctionKind::Reverse),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
+/// How the price of the Dutch auction decreases from the initial price to the floor price
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DecayCurve {
+    /// Decreases continuously at a constant rate until the auction ends
+    Linear,
+    /// Decreases by the same amount at every interval until the auction ends
+    Stepwise,
+    /// Decreases by the same rate at every interval
+    Exponential,
+}
+
+impl TryFrom<u8> for DecayCurve {
+    type Error = ProgramError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(DecayCurve::Linear),
+            1 => Ok(DecayCurve::Stepwise),
+            2 => Ok(DecayCurve::Exponential),
+            _ => Err(ProgramError::InvalidAccountData),
+        }
+    }
+}
+
 pub struct Auction {
     pub is_initialized: bool,
-    /// Wallet pubkey of exhibitor
+    /// Wallet pubkey of exhibitor, which is the buyer procuring the token in the reverse auction
     pub exhibitor_pubkey: Pubkey,
-    /// Exhibiting NFT pubkey of exhibitor in escrow
+    /// Exhibiting NFT pubkey of exhibitor in escrow, which holds the budget in the reverse auction
     pub exhibiting_nft_temp_pubkey: Pubkey,
-    /// FT pubkey of exhibitor to get token into
+    /// FT pubkey of exhibitor to get token into, which receives the procured token in the reverse auction
     pub exhibitor_ft_receiving_pubkey: Pubkey,
-    /// Current price
+    /// Current price, which is the lowest ask in the reverse auction
     pub price: u64,
     /// Auction end time
     pub end_at: i64,
-    /// Wallet pubkey of highest bidder
+    /// Wallet pubkey of highest bidder, which is the seller with the lowest ask in the reverse auction
     pub highest_bidder_pubkey: Pubkey,
-    /// FT pubkey of highest bidder in escrow
+    /// FT pubkey of highest bidder in escrow, which holds the token deposited by the seller in the reverse auction
     pub highest_bidder_ft_temp_pubkey: Pubkey,
     /// FT pubkey of highest bidder to return to when the auction cancel or other bidder become the highest bidder.
     pub highest_bidder_ft_returning_pubkey: Pubkey,
+    /// Bump seed of the PDA derived from the escrow account, which is the authority of the escrowed tokens
+    pub bump_seed: u8,
+    /// Mint pubkey of FT accepted for bidding, which is the token procured by the reverse auction
+    pub ft_mint_pubkey: Pubkey,
+    /// Mint pubkey of the exhibiting NFT, which is the FT of the budget in the reverse auction (see `budget_mint`)
+    pub nft_mint_pubkey: Pubkey,
+    /// NFT pubkey of highest bidder to receive the exhibiting NFT when the auction is settled, or the payment from the budget in the reverse auction
+    pub highest_bidder_nft_receiving_pubkey: Pubkey,
+    /// NFT pubkey of exhibitor to return the exhibiting NFT to when the auction ends without any bidder, or the rest of the budget in the reverse auction
+    pub exhibitor_nft_pubkey: Pubkey,
+    /// Minimum price the exhibitor will accept, 0 if there is no visible reserve price
+    pub reserve_price: u64,
+    /// Hash of the hidden reserve price and the salt revealed at settlement, all zeros if there is no hidden reserve price
+    pub reserve_price_hash: [u8; 32],
+    /// Minimum amount by which a bid must exceed the current price
+    pub min_increment: u64,
+    /// Minimum rate in basis points by which a bid must exceed the current price
+    pub min_increment_bps: u16,
+    /// Bids placed within this many seconds before the end extend the auction, 0 if it never extends
+    pub extension_window: u64,
+    /// Seconds by which the end is extended on a late bid
+    pub extension_seconds: u64,
+    /// Latest time the end can be extended to, 0 if there is no limit
+    pub max_end_at: i64,
+    /// Time bidding opens
+    pub start_at: i64,
+    /// Price at which anyone can buy the NFT immediately, 0 if there is none
+    pub buy_now_price: u64,
+    /// Kind of the auction
+    pub kind: AuctionKind,
+    /// Lowest price the Dutch auction decreases to
+    pub floor_price: u64,
+    /// How the price of the Dutch auction decreases
+    pub decay_curve: DecayCurve,
+    /// Seconds between price decreases of the stepwise and exponential curves
+    pub decay_interval: u64,
+    /// Rate in basis points by which the price decreases at every interval of the exponential curve
+    pub decay_rate_bps: u16,
+    /// End time of the reveal phase of the sealed bid auction
+    pub reveal_end_at: i64,
+    /// Whether the deposit of the sealed bid not revealed is forfeited to the exhibitor
+    pub forfeit_unrevealed: bool,
+    /// Number of sealed bids committed
+    pub bid_count: u32,
+    /// Second highest revealed price of the sealed bid auction, or the initial price if there is none
+    pub second_price: u64,
+    /// Number of tokens of the exhibiting mint sold as one lot
+    pub quantity: u64,
+    /// Number of the NFTs exhibited in the lot in addition to the exhibiting NFT
+    pub bundle_len: u8,
+    /// PDA's temporary NFT accounts of the NFTs exhibited in the lot in addition to the exhibiting NFT
+    pub bundle_nft_temp_pubkeys: [Pubkey; MAX_BUNDLE_ITEMS],
+    /// Vault account holding the lamports of the bids of the native SOL auction, default if the FT is used
+    pub vault_pubkey: Pubkey,
+    /// Marketplace fee in basis points of the proceeds, taken from the fee config of the auction currency at the time of exhibiting
+    pub fee_bps: u16,
+    /// Token account of the marketplace to receive the fee
+    pub treasury_pubkey: Pubkey,
+    /// Creator royalties in basis points of the price, taken from the metadata of the exhibiting NFT at the time of exhibiting
+    pub royalty_bps: u16,
+    /// Number of the verified creators receiving the royalties
+    pub creators_len: u8,
+    /// Wallet pubkeys of the verified creators receiving the royalties
+    pub creator_pubkeys: [Pubkey; MAX_CREATORS],
+    /// Shares in percent of the royalties of the verified creators
+    pub creator_shares: [u8; MAX_CREATORS],
+    /// Referral fee in basis points paid to the referrer of the successful bidder, taken from the fee config at the time of exhibiting
+    pub referral_bps: u16,
+    /// Whether the referral fee is a share of the marketplace fee rather than of the proceeds
+    pub referral_from_fee: bool,
+    /// FT pubkey of the referrer who brought the highest bidder, if any
+    pub highest_bidder_referrer_pubkey: Pubkey,
+    /// Auction house the auction is exhibited in, or the default pubkey outside of any house
+    pub auction_house_pubkey: Pubkey,
+}
+
+impl Auction {
+    /// Finds the escrow account of the auction exhibiting the NFT of the given mint in the given auction house
+    /// The auctions outside of any house are seeded by the default pubkey, and every other PDA of the auction is derived from the escrow account.
+    pub fn find_escrow_address(
+        auction_house: &Pubkey,
+        exhibitor: &Pubkey,
+        nft_mint: &Pubkey,
+        program_id: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                ESCROW_SEED,
+                auction_house.as_ref(),
+                exhibitor.as_ref(),
+                nft_mint.as_ref(),
+            ],
+            program_id,
+        )
+    }
+
+    /// Finds the PDA that owns the tokens deposited in the auction held by the given escrow account
+    pub fn find_authority_address(escrow: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[AUTHORITY_SEED, escrow.as_ref()], program_id)
+    }
+
+    /// Seeds to sign as the PDA that owns the tokens deposited in the auction
+    pub fn authority_seeds<'a>(escrow: &'a Pubkey, bump_seed: &'a [u8; 1]) -> [&'a [u8]; 3] {
+        [AUTHORITY_SEED, escrow.as_ref(), bump_seed]
+    }
+
+    /// Finds the vault account holding the lamports of the native SOL auction held by the given escrow account
+    pub fn find_vault_address(escrow: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[VAULT_SEED, escrow.as_ref()], program_id)
+    }
+
+    /// Finds the wSOL account wrapping the lamports bid by the given bidder on the auction held by the given escrow account
+    pub fn find_wsol_address(
+        escrow: &Pubkey,
+        bidder: &Pubkey,
+        program_id: &Pubkey,
+    ) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[WSOL_SEED, escrow.as_ref(), bidder.as_ref()], program_id)
+    }
+
+    /// Mint of the currency the auction is priced in, which determines the marketplace fee
+    /// The native lamports are charged as the wrapped SOL, and the reverse auction is priced in the budget.
+    pub fn currency_mint(&self) -> Pubkey {
+        if self.kind == AuctionKind::Reverse {
+            self.nft_mint_pubkey
+        } else if self.is_native() {
+            spl_token::native_mint::id()
+        } else {
+            self.ft_mint_pubkey
+        }
+    }
+
+    /// Whether the bids are paid in native lamports instead of the FT
+    pub fn is_native(&self) -> bool {
+        self.vault_pubkey != Pubkey::default()
+    }
+
+    /// PDA's temporary NFT accounts of the NFTs exhibited in the lot in addition to the exhibiting NFT
+    pub fn bundle(&self) -> &[Pubkey] {
+        &self.bundle_nft_temp_pubkeys[..self.bundle_len as usize]
+    }
+
+    /// Verified creators receiving the royalties and their shares in percent
+    pub fn creators(&self) -> impl Iterator<Item = (&Pubkey, u8)> {
+        self.creator_pubkeys
+            .iter()
+            .zip(self.creator_shares)
+            .take(self.creators_len as usize)
+    }
+
+    /// Mint of the budget escrowed by the buyer of the reverse auction, which takes the place of the exhibiting NFT
+    pub fn budget_mint(&self) -> &Pubkey {
+        &self.nft_mint_pubkey
+    }
+
+    /// Whether the bids are sealed until the reveal phase
+    pub fn is_sealed(&self) -> bool {
+        self.kind == AuctionKind::SealedFirstPrice || self.kind == AuctionKind::SealedSecondPrice
+    }
+
+    /// Time after which the auction can be settled, which is the end of the reveal phase for the sealed bid auction
+    pub fn settle_at(&self) -> i64 {
+        if self.is_sealed() {
+            self.reveal_end_at
+        } else {
+            self.end_at
+        }
+    }
 }
 
 impl Sealed for Auction {}
@@ -36,7 +295,7 @@ impl IsInitialized for Auction {
 }
 
 impl Pack for Auction {
-    const LEN: usize = 209;
+    const LEN: usize = 907;
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let dst = array_mut_ref![dst, 0, Auction::LEN];
@@ -50,7 +309,47 @@ impl Pack for Auction {
             highest_bidder_pubkey_dst,
             highest_bidder_ft_temp_pubkey_dst,
             highest_bidder_ft_returning_pubkey_dst,
-        ) = mut_array_refs![dst, 1, 32, 32, 32, 8, 8, 32, 32, 32];
+            bump_seed_dst,
+            ft_mint_pubkey_dst,
+            nft_mint_pubkey_dst,
+            highest_bidder_nft_receiving_pubkey_dst,
+            exhibitor_nft_pubkey_dst,
+            reserve_price_dst,
+            reserve_price_hash_dst,
+            min_increment_dst,
+            min_increment_bps_dst,
+            extension_window_dst,
+            extension_seconds_dst,
+            max_end_at_dst,
+            start_at_dst,
+            buy_now_price_dst,
+            kind_dst,
+            floor_price_dst,
+            decay_curve_dst,
+            decay_interval_dst,
+            decay_rate_bps_dst,
+            reveal_end_at_dst,
+            forfeit_unrevealed_dst,
+            bid_count_dst,
+            second_price_dst,
+            quantity_dst,
+            bundle_len_dst,
+            bundle_nft_temp_pubkeys_dst,
+            vault_pubkey_dst,
+            fee_bps_dst,
+            treasury_pubkey_dst,
+            royalty_bps_dst,
+            creators_len_dst,
+            creator_pubkeys_dst,
+            creator_shares_dst,
+            referral_bps_dst,
+            referral_from_fee_dst,
+            highest_bidder_referrer_pubkey_dst,
+            auction_house_pubkey_dst,
+        ) = mut_array_refs![
+            dst, 1, 32, 32, 32, 8, 8, 32, 32, 32, 1, 32, 32, 32, 32, 8, 32, 8, 2, 8, 8, 8, 8, 8, 1,
+            8, 1, 8, 2, 8, 1, 4, 8, 8, 1, 128, 32, 2, 32, 2, 1, 160, 5, 2, 1, 32, 32
+        ];
 
         let Auction {
             is_initialized,
@@ -62,6 +361,43 @@ impl Pack for Auction {
             highest_bidder_pubkey,
             highest_bidder_ft_temp_pubkey,
             highest_bidder_ft_returning_pubkey,
+            bump_seed,
+            ft_mint_pubkey,
+            nft_mint_pubkey,
+            highest_bidder_nft_receiving_pubkey,
+            exhibitor_nft_pubkey,
+            reserve_price,
+            reserve_price_hash,
+            min_increment,
+            min_increment_bps,
+            extension_window,
+            extension_seconds,
+            max_end_at,
+            start_at,
+            buy_now_price,
+            kind,
+            floor_price,
+            decay_curve,
+            decay_interval,
+            decay_rate_bps,
+            reveal_end_at,
+            forfeit_unrevealed,
+            bid_count,
+            second_price,
+            quantity,
+            bundle_len,
+            bundle_nft_temp_pubkeys,
+            vault_pubkey,
+            fee_bps,
+            treasury_pubkey,
+            royalty_bps,
+            creators_len,
+            creator_pubkeys,
+            creator_shares,
+            referral_bps,
+            referral_from_fee,
+            highest_bidder_referrer_pubkey,
+            auction_house_pubkey,
         } = self;
 
         is_initialized_dst[0] = *is_initialized as u8;
@@ -74,6 +410,54 @@ impl Pack for Auction {
         highest_bidder_ft_temp_pubkey_dst.copy_from_slice(highest_bidder_ft_temp_pubkey.as_ref());
         highest_bidder_ft_returning_pubkey_dst
             .copy_from_slice(highest_bidder_ft_returning_pubkey.as_ref());
+        bump_seed_dst[0] = *bump_seed;
+        ft_mint_pubkey_dst.copy_from_slice(ft_mint_pubkey.as_ref());
+        nft_mint_pubkey_dst.copy_from_slice(nft_mint_pubkey.as_ref());
+        highest_bidder_nft_receiving_pubkey_dst
+            .copy_from_slice(highest_bidder_nft_receiving_pubkey.as_ref());
+        exhibitor_nft_pubkey_dst.copy_from_slice(exhibitor_nft_pubkey.as_ref());
+        *reserve_price_dst = reserve_price.to_le_bytes();
+        reserve_price_hash_dst.copy_from_slice(reserve_price_hash);
+        *min_increment_dst = min_increment.to_le_bytes();
+        *min_increment_bps_dst = min_increment_bps.to_le_bytes();
+        *extension_window_dst = extension_window.to_le_bytes();
+        *extension_seconds_dst = extension_seconds.to_le_bytes();
+        *max_end_at_dst = max_end_at.to_le_bytes();
+        *start_at_dst = start_at.to_le_bytes();
+        *buy_now_price_dst = buy_now_price.to_le_bytes();
+        kind_dst[0] = *kind as u8;
+        *floor_price_dst = floor_price.to_le_bytes();
+        decay_curve_dst[0] = *decay_curve as u8;
+        *decay_interval_dst = decay_interval.to_le_bytes();
+        *decay_rate_bps_dst = decay_rate_bps.to_le_bytes();
+        *reveal_end_at_dst = reveal_end_at.to_le_bytes();
+        forfeit_unrevealed_dst[0] = *forfeit_unrevealed as u8;
+        *bid_count_dst = bid_count.to_le_bytes();
+        *second_price_dst = second_price.to_le_bytes();
+        *quantity_dst = quantity.to_le_bytes();
+        bundle_len_dst[0] = *bundle_len;
+        for (dst, pubkey) in bundle_nft_temp_pubkeys_dst
+            .chunks_exact_mut(32)
+            .zip(bundle_nft_temp_pubkeys)
+        {
+            dst.copy_from_slice(pubkey.as_ref());
+        }
+        vault_pubkey_dst.copy_from_slice(vault_pubkey.as_ref());
+        *fee_bps_dst = fee_bps.to_le_bytes();
+        treasury_pubkey_dst.copy_from_slice(treasury_pubkey.as_ref());
+        *royalty_bps_dst = royalty_bps.to_le_bytes();
+        creators_len_dst[0] = *creators_len;
+        for (dst, pubkey) in creator_pubkeys_dst
+            .chunks_exact_mut(32)
+            .zip(creator_pubkeys)
+        {
+            dst.copy_from_slice(pubkey.as_ref());
+        }
+        *creator_shares_dst = *creator_shares;
+        *referral_bps_dst = referral_bps.to_le_bytes();
+        referral_from_fee_dst[0] = *referral_from_fee as u8;
+        highest_bidder_referrer_pubkey_dst.copy_from_slice(highest_bidder_referrer_pubkey.as_ref());
+        auction_house_pubkey_dst.copy_from_slice(auction_house_pubkey.as_ref());
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
@@ -88,12 +472,48 @@ impl Pack for Auction {
             highest_bidder_pubkey,
             highest_bidder_ft_temp_pubkey,
             highest_bidder_ft_returning_pubkey,
-        ) = array_refs![src, 1, 32, 32, 32, 8, 8, 32, 32, 32];
-        let is_initialized = match is_initialized {
-            [0] => false,
-            [1] => true,
-            _ => return Err(ProgramError::InvalidAccountData),
-        };
+            bump_seed,
+            ft_mint_pubkey,
+            nft_mint_pubkey,
+            highest_bidder_nft_receiving_pubkey,
+            exhibitor_nft_pubkey,
+            reserve_price,
+            reserve_price_hash,
+            min_increment,
+            min_increment_bps,
+            extension_window,
+            extension_seconds,
+            max_end_at,
+            start_at,
+            buy_now_price,
+            kind,
+            floor_price,
+            decay_curve,
+            decay_interval,
+            decay_rate_bps,
+            reveal_end_at,
+            forfeit_unrevealed,
+            bid_count,
+            second_price,
+            quantity,
+            bundle_len,
+            bundle_nft_temp_pubkeys,
+            vault_pubkey,
+            fee_bps,
+            treasury_pubkey,
+            royalty_bps,
+            creators_len,
+            creator_pubkeys,
+            creator_shares,
+            referral_bps,
+            referral_from_fee,
+            highest_bidder_referrer_pubkey,
+            auction_house_pubkey,
+        ) = array_refs![
+            src, 1, 32, 32, 32, 8, 8, 32, 32, 32, 1, 32, 32, 32, 32, 8, 32, 8, 2, 8, 8, 8, 8, 8, 1,
+            8, 1, 8, 2, 8, 1, 4, 8, 8, 1, 128, 32, 2, 32, 2, 1, 160, 5, 2, 1, 32, 32
+        ];
+        let is_initialized = unpack_bool(is_initialized)?;
 
         Ok(Auction {
             is_initialized,
@@ -107,6 +527,863 @@ impl Pack for Auction {
             highest_bidder_ft_returning_pubkey: Pubkey::new_from_array(
                 *highest_bidder_ft_returning_pubkey,
             ),
+            bump_seed: bump_seed[0],
+            ft_mint_pubkey: Pubkey::new_from_array(*ft_mint_pubkey),
+            nft_mint_pubkey: Pubkey::new_from_array(*nft_mint_pubkey),
+            highest_bidder_nft_receiving_pubkey: Pubkey::new_from_array(
+                *highest_bidder_nft_receiving_pubkey,
+            ),
+            exhibitor_nft_pubkey: Pubkey::new_from_array(*exhibitor_nft_pubkey),
+            reserve_price: u64::from_le_bytes(*reserve_price),
+            reserve_price_hash: *reserve_price_hash,
+            min_increment: u64::from_le_bytes(*min_increment),
+            min_increment_bps: u16::from_le_bytes(*min_increment_bps),
+            extension_window: u64::from_le_bytes(*extension_window),
+            extension_seconds: u64::from_le_bytes(*extension_seconds),
+            max_end_at: i64::from_le_bytes(*max_end_at),
+            start_at: i64::from_le_bytes(*start_at),
+            buy_now_price: u64::from_le_bytes(*buy_now_price),
+            kind: AuctionKind::try_from(kind[0])?,
+            floor_price: u64::from_le_bytes(*floor_price),
+            decay_curve: DecayCurve::try_from(decay_curve[0])?,
+            decay_interval: u64::from_le_bytes(*decay_interval),
+            decay_rate_bps: u16::from_le_bytes(*decay_rate_bps),
+            reveal_end_at: i64::from_le_bytes(*reveal_end_at),
+            forfeit_unrevealed: unpack_bool(forfeit_unrevealed)?,
+            bid_count: u32::from_le_bytes(*bid_count),
+            second_price: u64::from_le_bytes(*second_price),
+            quantity: u64::from_le_bytes(*quantity),
+            bundle_len: bundle_len[0],
+            bundle_nft_temp_pubkeys: unpack_pubkeys(bundle_nft_temp_pubkeys),
+            vault_pubkey: Pubkey::new_from_array(*vault_pubkey),
+            fee_bps: u16::from_le_bytes(*fee_bps),
+            treasury_pubkey: Pubkey::new_from_array(*treasury_pubkey),
+            royalty_bps: u16::from_le_bytes(*royalty_bps),
+            creators_len: creators_len[0],
+            creator_pubkeys: unpack_pubkeys(creator_pubkeys),
+            creator_shares: *creator_shares,
+            referral_bps: u16::from_le_bytes(*referral_bps),
+            referral_from_fee: unpack_bool(referral_from_fee)?,
+            highest_bidder_referrer_pubkey: Pubkey::new_from_array(*highest_bidder_referrer_pubkey),
+            auction_house_pubkey: Pubkey::new_from_array(*auction_house_pubkey),
+        })
+    }
+}
+
+/// Sealed bid of a bidder, held by the PDA derived from the escrow account and the bidder
+pub struct SealedBid {
+    pub is_initialized: bool,
+    /// Escrow account of the auction bid on
+    pub escrow_pubkey: Pubkey,
+    /// Wallet pubkey of bidder
+    pub bidder_pubkey: Pubkey,
+    /// Hash of the bidding price and a salt, revealed after the commit phase
+    pub commitment: [u8; 32],
+    /// Amount of FT deposited, which must cover the bidding price
+    pub deposit: u64,
+    /// FT pubkey of bidder in escrow holding the deposit
+    pub bidder_ft_temp_pubkey: Pubkey,
+    /// FT pubkey of bidder to return the deposit to
+    pub bidder_ft_returning_pubkey: Pubkey,
+    /// NFT pubkey of bidder to receive the exhibiting NFT when the bid wins
+    pub bidder_nft_receiving_pubkey: Pubkey,
+    /// FT pubkey of exhibitor to forfeit the deposit to when the bid is not revealed
+    pub exhibitor_ft_receiving_pubkey: Pubkey,
+    /// Whether the deposit is forfeited when the bid is not revealed
+    pub forfeit_unrevealed: bool,
+    /// Whether the bid is revealed
+    pub is_revealed: bool,
+    /// Revealed bidding price
+    pub price: u64,
+    /// Bump seed of the PDA derived from the escrow account, which is the authority of the deposit
+    pub bump_seed: u8,
+}
+
+impl SealedBid {
+    /// Finds the sealed bid account of the bidder on the auction held by the given escrow account
+    pub fn find_address(escrow: &Pubkey, bidder: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[BID_SEED, escrow.as_ref(), bidder.as_ref()], program_id)
+    }
+}
+
+impl Sealed for SealedBid {}
+
+impl IsInitialized for SealedBid {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for SealedBid {
+    const LEN: usize = 244;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, SealedBid::LEN];
+        let (
+            is_initialized_dst,
+            escrow_pubkey_dst,
+            bidder_pubkey_dst,
+            commitment_dst,
+            deposit_dst,
+            bidder_ft_temp_pubkey_dst,
+            bidder_ft_returning_pubkey_dst,
+            bidder_nft_receiving_pubkey_dst,
+            exhibitor_ft_receiving_pubkey_dst,
+            forfeit_unrevealed_dst,
+            is_revealed_dst,
+            price_dst,
+            bump_seed_dst,
+        ) = mut_array_refs![dst, 1, 32, 32, 32, 8, 32, 32, 32, 32, 1, 1, 8, 1];
+
+        let SealedBid {
+            is_initialized,
+            escrow_pubkey,
+            bidder_pubkey,
+            commitment,
+            deposit,
+            bidder_ft_temp_pubkey,
+            bidder_ft_returning_pubkey,
+            bidder_nft_receiving_pubkey,
+            exhibitor_ft_receiving_pubkey,
+            forfeit_unrevealed,
+            is_revealed,
+            price,
+            bump_seed,
+        } = self;
+
+        is_initialized_dst[0] = *is_initialized as u8;
+        escrow_pubkey_dst.copy_from_slice(escrow_pubkey.as_ref());
+        bidder_pubkey_dst.copy_from_slice(bidder_pubkey.as_ref());
+        commitment_dst.copy_from_slice(commitment);
+        *deposit_dst = deposit.to_le_bytes();
+        bidder_ft_temp_pubkey_dst.copy_from_slice(bidder_ft_temp_pubkey.as_ref());
+        bidder_ft_returning_pubkey_dst.copy_from_slice(bidder_ft_returning_pubkey.as_ref());
+        bidder_nft_receiving_pubkey_dst.copy_from_slice(bidder_nft_receiving_pubkey.as_ref());
+        exhibitor_ft_receiving_pubkey_dst.copy_from_slice(exhibitor_ft_receiving_pubkey.as_ref());
+        forfeit_unrevealed_dst[0] = *forfeit_unrevealed as u8;
+        is_revealed_dst[0] = *is_revealed as u8;
+        *price_dst = price.to_le_bytes();
+        bump_seed_dst[0] = *bump_seed;
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, SealedBid::LEN];
+        let (
+            is_initialized,
+            escrow_pubkey,
+            bidder_pubkey,
+            commitment,
+            deposit,
+            bidder_ft_temp_pubkey,
+            bidder_ft_returning_pubkey,
+            bidder_nft_receiving_pubkey,
+            exhibitor_ft_receiving_pubkey,
+            forfeit_unrevealed,
+            is_revealed,
+            price,
+            bump_seed,
+        ) = array_refs![src, 1, 32, 32, 32, 8, 32, 32, 32, 32, 1, 1, 8, 1];
+
+        Ok(SealedBid {
+            is_initialized: unpack_bool(is_initialized)?,
+            escrow_pubkey: Pubkey::new_from_array(*escrow_pubkey),
+            bidder_pubkey: Pubkey::new_from_array(*bidder_pubkey),
+            commitment: *commitment,
+            deposit: u64::from_le_bytes(*deposit),
+            bidder_ft_temp_pubkey: Pubkey::new_from_array(*bidder_ft_temp_pubkey),
+            bidder_ft_returning_pubkey: Pubkey::new_from_array(*bidder_ft_returning_pubkey),
+            bidder_nft_receiving_pubkey: Pubkey::new_from_array(*bidder_nft_receiving_pubkey),
+            exhibitor_ft_receiving_pubkey: Pubkey::new_from_array(*exhibitor_ft_receiving_pubkey),
+            forfeit_unrevealed: unpack_bool(forfeit_unrevealed)?,
+            is_revealed: unpack_bool(is_revealed)?,
+            price: u64::from_le_bytes(*price),
+            bump_seed: bump_seed[0],
+        })
+    }
+}
+
+/// Bid held in the bid book of the uniform-price auction
+pub struct BidEntry {
+    /// Wallet pubkey of bidder
+    pub bidder_pubkey: Pubkey,
+    /// FT pubkey of bidder in escrow
+    pub bidder_ft_temp_pubkey: Pubkey,
+    /// FT pubkey of bidder to return to when the bid falls out of the book or the price is settled
+    pub bidder_ft_returning_pubkey: Pubkey,
+    /// NFT pubkey of bidder to receive a unit when the auction is settled
+    pub bidder_nft_receiving_pubkey: Pubkey,
+    /// Bidding price
+    pub price: u64,
+}
+
+impl Sealed for BidEntry {}
+
+impl Pack for BidEntry {
+    const LEN: usize = 136;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, BidEntry::LEN];
+        let (
+            bidder_pubkey_dst,
+            bidder_ft_temp_pubkey_dst,
+            bidder_ft_returning_pubkey_dst,
+            bidder_nft_receiving_pubkey_dst,
+            price_dst,
+        ) = mut_array_refs![dst, 32, 32, 32, 32, 8];
+
+        let BidEntry {
+            bidder_pubkey,
+            bidder_ft_temp_pubkey,
+            bidder_ft_returning_pubkey,
+            bidder_nft_receiving_pubkey,
+            price,
+        } = self;
+
+        bidder_pubkey_dst.copy_from_slice(bidder_pubkey.as_ref());
+        bidder_ft_temp_pubkey_dst.copy_from_slice(bidder_ft_temp_pubkey.as_ref());
+        bidder_ft_returning_pubkey_dst.copy_from_slice(bidder_ft_returning_pubkey.as_ref());
+        bidder_nft_receiving_pubkey_dst.copy_from_slice(bidder_nft_receiving_pubkey.as_ref());
+        *price_dst = price.to_le_bytes();
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, BidEntry::LEN];
+        let (
+            bidder_pubkey,
+            bidder_ft_temp_pubkey,
+            bidder_ft_returning_pubkey,
+            bidder_nft_receiving_pubkey,
+            price,
+        ) = array_refs![src, 32, 32, 32, 32, 8];
+
+        Ok(BidEntry {
+            bidder_pubkey: Pubkey::new_from_array(*bidder_pubkey),
+            bidder_ft_temp_pubkey: Pubkey::new_from_array(*bidder_ft_temp_pubkey),
+            bidder_ft_returning_pubkey: Pubkey::new_from_array(*bidder_ft_returning_pubkey),
+            bidder_nft_receiving_pubkey: Pubkey::new_from_array(*bidder_nft_receiving_pubkey),
+            price: u64::from_le_bytes(*price),
+        })
+    }
+}
+
+/// Currency allowed in an auction house and the treasury account of the house to receive the fee in it
+#[derive(Clone, Copy, Default)]
+pub struct HouseCurrency {
+    /// Mint of the currency
+    pub mint_pubkey: Pubkey,
+    /// Token account of the house to receive the fee
+    pub treasury_pubkey: Pubkey,
+}
+
+/// Marketplace running its own auctions with its own fee and currencies, held by the PDA derived from its authority
+pub struct AuctionHouse {
+    pub is_initialized: bool,
+    /// Wallet pubkey of the authority of the house
+    pub authority_pubkey: Pubkey,
+    /// Fee in basis points of the proceeds
+    pub fee_bps: u16,
+    /// Referral fee in basis points paid to the referrer of the successful bidder
+    pub referral_bps: u16,
+    /// Whether the referral fee is a share of the marketplace fee rather than of the proceeds
+    pub referral_from_fee: bool,
+    /// Bump seed of the house account
+    pub bump_seed: u8,
+    /// Currencies the auctions in the house can be priced in
+    pub currencies: Vec<HouseCurrency>,
+}
+
+impl AuctionHouse {
+    /// Finds the auction house account of the given authority
+    pub fn find_address(authority: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[HOUSE_SEED, authority.as_ref()], program_id)
+    }
+
+    /// Returns the currency of the given mint if it's allowed in the house
+    pub fn currency(&self, mint: &Pubkey) -> Option<&HouseCurrency> {
+        self.currencies
+            .iter()
+            .find(|currency| currency.mint_pubkey == *mint)
+    }
+}
+
+impl Sealed for AuctionHouse {}
+
+impl IsInitialized for AuctionHouse {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for AuctionHouse {
+    const LEN: usize = 40 + 64 * MAX_HOUSE_CURRENCIES;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, AuctionHouse::LEN];
+        let (
+            is_initialized_dst,
+            authority_pubkey_dst,
+            fee_bps_dst,
+            referral_bps_dst,
+            referral_from_fee_dst,
+            bump_seed_dst,
+            len_dst,
+            currencies_dst,
+        ) = mut_array_refs![dst, 1, 32, 2, 2, 1, 1, 1, 64 * MAX_HOUSE_CURRENCIES];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        authority_pubkey_dst.copy_from_slice(self.authority_pubkey.as_ref());
+        *fee_bps_dst = self.fee_bps.to_le_bytes();
+        *referral_bps_dst = self.referral_bps.to_le_bytes();
+        referral_from_fee_dst[0] = self.referral_from_fee as u8;
+        bump_seed_dst[0] = self.bump_seed;
+        len_dst[0] = self.currencies.len() as u8;
+        currencies_dst.fill(0);
+        for (currency, currency_dst) in self
+            .currencies
+            .iter()
+            .zip(currencies_dst.chunks_exact_mut(64))
+        {
+            currency_dst[..32].copy_from_slice(currency.mint_pubkey.as_ref());
+            currency_dst[32..].copy_from_slice(currency.treasury_pubkey.as_ref());
+        }
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, AuctionHouse::LEN];
+        let (
+            is_initialized,
+            authority_pubkey,
+            fee_bps,
+            referral_bps,
+            referral_from_fee,
+            bump_seed,
+            len,
+            currencies,
+        ) = array_refs![src, 1, 32, 2, 2, 1, 1, 1, 64 * MAX_HOUSE_CURRENCIES];
+        if len[0] as usize > MAX_HOUSE_CURRENCIES {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(AuctionHouse {
+            is_initialized: unpack_bool(is_initialized)?,
+            authority_pubkey: Pubkey::new_from_array(*authority_pubkey),
+            fee_bps: u16::from_le_bytes(*fee_bps),
+            referral_bps: u16::from_le_bytes(*referral_bps),
+            referral_from_fee: unpack_bool(referral_from_fee)?,
+            bump_seed: bump_seed[0],
+            currencies: currencies
+                .chunks_exact(64)
+                .take(len[0] as usize)
+                .map(|currency| HouseCurrency {
+                    mint_pubkey: Pubkey::new_from_array(*array_ref![currency, 0, 32]),
+                    treasury_pubkey: Pubkey::new_from_array(*array_ref![currency, 32, 32]),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Program-wide config administered by the admin authority, held by the PDA derived from `CONFIG_SEED`
+pub struct ProgramConfig {
+    pub is_initialized: bool,
+    /// Wallet pubkey of the admin authority
+    pub admin_pubkey: Pubkey,
+    /// Wallet pubkey of the admin authority proposed by the current one, until it accepts
+    pub pending_admin_pubkey: Pubkey,
+    /// Whether new auctions, bids and purchases are paused
+    pub paused: bool,
+}
+
+impl ProgramConfig {
+    /// Finds the program config account
+    pub fn find_address(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[CONFIG_SEED], program_id)
+    }
+}
+
+impl Sealed for ProgramConfig {}
+
+impl IsInitialized for ProgramConfig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for ProgramConfig {
+    const LEN: usize = 66;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, ProgramConfig::LEN];
+        let (is_initialized_dst, admin_pubkey_dst, pending_admin_pubkey_dst, paused_dst) =
+            mut_array_refs![dst, 1, 32, 32, 1];
+
+        let ProgramConfig {
+            is_initialized,
+            admin_pubkey,
+            pending_admin_pubkey,
+            paused,
+        } = self;
+
+        is_initialized_dst[0] = *is_initialized as u8;
+        admin_pubkey_dst.copy_from_slice(admin_pubkey.as_ref());
+        pending_admin_pubkey_dst.copy_from_slice(pending_admin_pubkey.as_ref());
+        paused_dst[0] = *paused as u8;
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, ProgramConfig::LEN];
+        let (is_initialized, admin_pubkey, pending_admin_pubkey, paused) =
+            array_refs![src, 1, 32, 32, 1];
+
+        Ok(ProgramConfig {
+            is_initialized: unpack_bool(is_initialized)?,
+            admin_pubkey: Pubkey::new_from_array(*admin_pubkey),
+            pending_admin_pubkey: Pubkey::new_from_array(*pending_admin_pubkey),
+            paused: unpack_bool(paused)?,
         })
     }
 }
+
+/// Marketplace fee charged on the proceeds of the auctions priced in a currency mint, held by the PDA derived from the mint
+pub struct FeeConfig {
+    pub is_initialized: bool,
+    /// Mint of the currency charged
+    pub ft_mint_pubkey: Pubkey,
+    /// Token account of the marketplace to receive the fee
+    pub treasury_pubkey: Pubkey,
+    /// Fee in basis points of the proceeds
+    pub fee_bps: u16,
+    /// Referral fee in basis points paid to the referrer of the successful bidder
+    pub referral_bps: u16,
+    /// Whether the referral fee is a share of the marketplace fee rather than of the proceeds
+    pub referral_from_fee: bool,
+}
+
+impl FeeConfig {
+    /// Finds the fee config account of the given currency mint
+    pub fn find_address(ft_mint: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[FEE_CONFIG_SEED, ft_mint.as_ref()], program_id)
+    }
+}
+
+impl Sealed for FeeConfig {}
+
+impl IsInitialized for FeeConfig {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for FeeConfig {
+    const LEN: usize = 70;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, FeeConfig::LEN];
+        let (
+            is_initialized_dst,
+            ft_mint_pubkey_dst,
+            treasury_pubkey_dst,
+            fee_bps_dst,
+            referral_bps_dst,
+            referral_from_fee_dst,
+        ) = mut_array_refs![dst, 1, 32, 32, 2, 2, 1];
+
+        let FeeConfig {
+            is_initialized,
+            ft_mint_pubkey,
+            treasury_pubkey,
+            fee_bps,
+            referral_bps,
+            referral_from_fee,
+        } = self;
+
+        is_initialized_dst[0] = *is_initialized as u8;
+        ft_mint_pubkey_dst.copy_from_slice(ft_mint_pubkey.as_ref());
+        treasury_pubkey_dst.copy_from_slice(treasury_pubkey.as_ref());
+        *fee_bps_dst = fee_bps.to_le_bytes();
+        *referral_bps_dst = referral_bps.to_le_bytes();
+        referral_from_fee_dst[0] = *referral_from_fee as u8;
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, FeeConfig::LEN];
+        let (
+            is_initialized,
+            ft_mint_pubkey,
+            treasury_pubkey,
+            fee_bps,
+            referral_bps,
+            referral_from_fee,
+        ) = array_refs![src, 1, 32, 32, 2, 2, 1];
+
+        Ok(FeeConfig {
+            is_initialized: unpack_bool(is_initialized)?,
+            ft_mint_pubkey: Pubkey::new_from_array(*ft_mint_pubkey),
+            treasury_pubkey: Pubkey::new_from_array(*treasury_pubkey),
+            fee_bps: u16::from_le_bytes(*fee_bps),
+            referral_bps: u16::from_le_bytes(*referral_bps),
+            referral_from_fee: unpack_bool(referral_from_fee)?,
+        })
+    }
+}
+
+/// Top bids of the uniform-price auction in descending order of price, held by the PDA derived from the escrow account
+pub struct BidBook {
+    pub is_initialized: bool,
+    /// Escrow account of the auction
+    pub escrow_pubkey: Pubkey,
+    /// Bids in descending order of price, the earlier one comes first if the prices are the same
+    pub entries: Vec<BidEntry>,
+}
+
+impl BidBook {
+    /// Finds the bid book of the auction held by the given escrow account
+    pub fn find_address(escrow: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[BID_BOOK_SEED, escrow.as_ref()], program_id)
+    }
+
+    /// Lowest bid, which falls out of the book by the next bid if the book is full
+    pub fn lowest_if_full(&self, capacity: u64) -> Option<&BidEntry> {
+        if self.entries.len() as u64 >= capacity {
+            self.entries.last()
+        } else {
+            None
+        }
+    }
+
+    /// Price every winner pays, which is the lowest bid in the book, or the initial price if there is none
+    pub fn clearing_price(&self, initial_price: u64) -> u64 {
+        self.entries
+            .last()
+            .map_or(initial_price, |entry| entry.price)
+    }
+
+    pub fn contains(&self, bidder: &Pubkey) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.bidder_pubkey == *bidder)
+    }
+
+    /// Inserts the bid keeping the descending order of price
+    pub fn insert(&mut self, entry: BidEntry) {
+        let index = self
+            .entries
+            .iter()
+            .position(|e| e.price < entry.price)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(index, entry);
+    }
+}
+
+impl Sealed for BidBook {}
+
+impl IsInitialized for BidBook {
+    fn is_initialized(&self) -> bool {
+        self.is_initialized
+    }
+}
+
+impl Pack for BidBook {
+    const LEN: usize = 34 + BidEntry::LEN * MAX_UNIFORM_WINNERS;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let dst = array_mut_ref![dst, 0, BidBook::LEN];
+        let (is_initialized_dst, escrow_pubkey_dst, len_dst, entries_dst) =
+            mut_array_refs![dst, 1, 32, 1, BidEntry::LEN * MAX_UNIFORM_WINNERS];
+
+        is_initialized_dst[0] = self.is_initialized as u8;
+        escrow_pubkey_dst.copy_from_slice(self.escrow_pubkey.as_ref());
+        len_dst[0] = self.entries.len() as u8;
+        entries_dst.fill(0);
+        for (entry, entry_dst) in self
+            .entries
+            .iter()
+            .zip(entries_dst.chunks_exact_mut(BidEntry::LEN))
+        {
+            entry.pack_into_slice(entry_dst);
+        }
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        let src = array_ref![src, 0, BidBook::LEN];
+        let (is_initialized, escrow_pubkey, len, entries) =
+            array_refs![src, 1, 32, 1, BidEntry::LEN * MAX_UNIFORM_WINNERS];
+        if len[0] as usize > MAX_UNIFORM_WINNERS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(BidBook {
+            is_initialized: unpack_bool(is_initialized)?,
+            escrow_pubkey: Pubkey::new_from_array(*escrow_pubkey),
+            entries: entries
+                .chunks_exact(BidEntry::LEN)
+                .take(len[0] as usize)
+                .map(BidEntry::unpack_from_slice)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Creator royalties of an NFT, read from its metadata account of the Metaplex Token Metadata program
+pub struct Royalty {
+    /// Royalties in basis points of the price
+    pub seller_fee_basis_points: u16,
+    /// Verified creators and their shares in percent of the royalties
+    pub creators: Vec<(Pubkey, u8)>,
+}
+
+impl Royalty {
+    /// Finds the metadata account of the given mint
+    pub fn find_metadata_address(mint: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[
+                METADATA_SEED,
+                TOKEN_METADATA_PROGRAM_ID.as_ref(),
+                mint.as_ref(),
+            ],
+            &TOKEN_METADATA_PROGRAM_ID,
+        )
+    }
+
+    /// Reads the royalties from the Borsh-serialized `Metadata` of the given mint
+    /// Only the verified creators are paid, since anyone can list unverified ones in the metadata.
+    pub fn unpack_metadata(src: &[u8], mint: &Pubkey) -> Result<Self, ProgramError> {
+        // key, update authority and mint
+        let (header, mut rest) = split_at(src, 65)?;
+        let (key, _, metadata_mint) = array_refs![array_ref![header, 0, 65], 1, 32, 32];
+        if key[0] != METADATA_V1_KEY || metadata_mint != mint.as_ref() {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // name, symbol and uri
+        for _ in 0..3 {
+            let (len, tail) = split_at(rest, 4)?;
+            let len = u32::from_le_bytes(*array_ref![len, 0, 4]) as usize;
+            rest = split_at(tail, len)?.1;
+        }
+        let (seller_fee_basis_points, rest) = split_at(rest, 2)?;
+        let seller_fee_basis_points =
+            u16::from_le_bytes(*array_ref![seller_fee_basis_points, 0, 2]);
+        if seller_fee_basis_points > 10_000 {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let (has_creators, rest) = split_at(rest, 1)?;
+        let mut creators = Vec::new();
+        if unpack_bool(array_ref![has_creators, 0, 1])? {
+            let (len, mut rest) = split_at(rest, 4)?;
+            for _ in 0..u32::from_le_bytes(*array_ref![len, 0, 4]) {
+                let (creator, tail) = split_at(rest, 34)?;
+                let (address, verified, share) = array_refs![array_ref![creator, 0, 34], 32, 1, 1];
+                if unpack_bool(verified)? && share[0] > 0 {
+                    creators.push((Pubkey::new_from_array(*address), share[0]));
+                }
+                rest = tail;
+            }
+        }
+        if creators.len() > MAX_CREATORS {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Royalty {
+            seller_fee_basis_points,
+            creators,
+        })
+    }
+}
+
+/// Key of the `Metadata` account of the Metaplex Token Metadata program
+const METADATA_V1_KEY: u8 = 4;
+
+fn split_at(src: &[u8], mid: usize) -> Result<(&[u8], &[u8]), ProgramError> {
+    if src.len() < mid {
+        return Err(ProgramError::InvalidAccountData);
+    }
+    Ok(src.split_at(mid))
+}
+
+fn unpack_bool(src: &[u8; 1]) -> Result<bool, ProgramError> {
+    match src {
+        [0] => Ok(false),
+        [1] => Ok(true),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+fn unpack_pubkeys<const N: usize>(src: &[u8]) -> [Pubkey; N] {
+    let mut pubkeys = [Pubkey::default(); N];
+    for (pubkey, src) in pubkeys.iter_mut().zip(src.chunks_exact(32)) {
+        *pubkey = Pubkey::new_from_array(*array_ref![src, 0, 32]);
+    }
+    pubkeys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(price: u64) -> BidEntry {
+        BidEntry {
+            bidder_pubkey: Pubkey::new_unique(),
+            bidder_ft_temp_pubkey: Pubkey::new_unique(),
+            bidder_ft_returning_pubkey: Pubkey::new_unique(),
+            bidder_nft_receiving_pubkey: Pubkey::new_unique(),
+            price,
+        }
+    }
+
+    fn book(prices: &[u64]) -> BidBook {
+        let mut bid_book = BidBook {
+            is_initialized: true,
+            escrow_pubkey: Pubkey::new_unique(),
+            entries: vec![],
+        };
+        for price in prices {
+            bid_book.insert(entry(*price));
+        }
+        bid_book
+    }
+
+    fn prices(bid_book: &BidBook) -> Vec<u64> {
+        bid_book.entries.iter().map(|entry| entry.price).collect()
+    }
+
+    #[test]
+    fn bid_book_keeps_descending_order_and_earlier_bids_first() {
+        let mut bid_book = book(&[200, 500, 300]);
+        assert_eq!(prices(&bid_book), [500, 300, 200]);
+
+        let tie = entry(300);
+        let tie_bidder = tie.bidder_pubkey;
+        bid_book.insert(tie);
+        assert_eq!(prices(&bid_book), [500, 300, 300, 200]);
+        assert_eq!(bid_book.entries[2].bidder_pubkey, tie_bidder);
+        assert!(bid_book.contains(&tie_bidder));
+    }
+
+    #[test]
+    fn bid_book_evicts_the_lowest_bid_only_when_full() {
+        let mut bid_book = book(&[300, 200]);
+        assert!(bid_book.lowest_if_full(3).is_none());
+
+        bid_book.insert(entry(100));
+        let lowest = bid_book.lowest_if_full(3).unwrap();
+        assert_eq!(lowest.price, 100);
+        let evicted = lowest.bidder_pubkey;
+
+        // A new bid takes the place of the lowest one, as the bid instruction does.
+        bid_book.entries.pop();
+        bid_book.insert(entry(250));
+        assert_eq!(prices(&bid_book), [300, 250, 200]);
+        assert!(!bid_book.contains(&evicted));
+    }
+
+    #[test]
+    fn bid_book_survives_packing() {
+        let bid_book = book(&[300, 200]);
+        let mut data = vec![0; BidBook::LEN];
+        BidBook::pack(bid_book, &mut data).unwrap();
+        assert_eq!(prices(&BidBook::unpack(&data).unwrap()), [300, 200]);
+    }
+
+    #[test]
+    fn clearing_price_is_the_lowest_bid_in_the_book() {
+        assert_eq!(book(&[300, 200, 250]).clearing_price(100), 200);
+        // Some units are left unsold, but the winners still pay the lowest winning bid.
+        assert_eq!(book(&[300]).clearing_price(100), 300);
+        assert_eq!(book(&[]).clearing_price(100), 100);
+    }
+
+    /// Serializes the `Metadata` of the given mint up to the creators, as the Metaplex Token Metadata program does
+    fn metadata(
+        mint: &Pubkey,
+        seller_fee_basis_points: u16,
+        creators: &[(Pubkey, bool, u8)],
+    ) -> Vec<u8> {
+        let mut data = vec![METADATA_V1_KEY];
+        data.extend_from_slice(Pubkey::new_unique().as_ref());
+        data.extend_from_slice(mint.as_ref());
+        for field in ["Auction NFT", "AUC", "https://example.com/nft.json"] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+        data.extend_from_slice(&seller_fee_basis_points.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&(creators.len() as u32).to_le_bytes());
+        for (address, verified, share) in creators {
+            data.extend_from_slice(address.as_ref());
+            data.push(*verified as u8);
+            data.push(*share);
+        }
+        // primary sale happened and is mutable
+        data.extend_from_slice(&[0, 1]);
+        data
+    }
+
+    #[test]
+    fn unpack_metadata_reads_verified_creators() {
+        let mint = Pubkey::new_unique();
+        let (verified, unverified, another) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let data = metadata(
+            &mint,
+            500,
+            &[
+                (verified, true, 60),
+                (unverified, false, 30),
+                (another, true, 10),
+            ],
+        );
+
+        let royalty = Royalty::unpack_metadata(&data, &mint).unwrap();
+        assert_eq!(royalty.seller_fee_basis_points, 500);
+        assert_eq!(royalty.creators, vec![(verified, 60), (another, 10)]);
+    }
+
+    #[test]
+    fn unpack_metadata_without_creators() {
+        let mint = Pubkey::new_unique();
+        let mut data = metadata(&mint, 250, &[]);
+        let has_creators = data.len() - 7;
+        data.truncate(has_creators);
+        data.extend_from_slice(&[0, 0, 1]);
+
+        let royalty = Royalty::unpack_metadata(&data, &mint).unwrap();
+        assert_eq!(royalty.seller_fee_basis_points, 250);
+        assert!(royalty.creators.is_empty());
+    }
+
+    #[test]
+    fn unpack_metadata_rejects_another_mint() {
+        let data = metadata(&Pubkey::new_unique(), 500, &[]);
+        assert_eq!(
+            Royalty::unpack_metadata(&data, &Pubkey::new_unique()).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn unpack_metadata_rejects_truncated_data() {
+        let mint = Pubkey::new_unique();
+        let data = metadata(&mint, 500, &[(Pubkey::new_unique(), true, 100)]);
+        // Cut in the middle of the creator, the name and the mint
+        for len in [data.len() - 10, 70, 40] {
+            assert_eq!(
+                Royalty::unpack_metadata(&data[..len], &mint).err(),
+                Some(ProgramError::InvalidAccountData)
+            );
+        }
+    }
+
+    #[test]
+    fn unpack_metadata_rejects_too_many_creators() {
+        let mint = Pubkey::new_unique();
+        let creators: Vec<_> = (0..MAX_CREATORS + 1)
+            .map(|_| (Pubkey::new_unique(), true, 1))
+            .collect();
+        let data = metadata(&mint, 500, &creators);
+        assert!(Royalty::unpack_metadata(&data, &mint).is_err());
+    }
+}
@@ -0,0 +1,1842 @@
+use crate::error::AuctionError;
+use crate::state::{
+    Auction, AuctionHouse, AuctionKind, BidBook, FeeConfig, ProgramConfig, Royalty, SealedBid,
+    HOUSE_SEED, MAX_BUNDLE_ITEMS, TOKEN_METADATA_PROGRAM_ID,
+};
+use solana_program::account_info::{next_account_info, AccountInfo};
+use solana_program::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_program::clock::Clock;
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use solana_program::program_utils::limited_deserialize;
+use solana_program::pubkey::Pubkey;
+use solana_program::rent::Rent;
+use solana_program::system_program;
+use solana_program::sysvar::{self, Sysvar};
+use spl_token::state::{Account as TokenAccount, Mint};
+
+/// Length of the metadata before the program data: the enum tag (4), the slot (8) and the optional upgrade authority (1 + 32)
+const PROGRAM_DATA_METADATA_LEN: u64 = 45;
+
+/// Accounts of `AuctionInstruction::Exhibit`
+pub struct ExhibitAccounts<'a, 'b> {
+    pub exhibitor_account: &'a AccountInfo<'b>,
+    pub exhibitor_nft_account: &'a AccountInfo<'b>,
+    pub exhibitor_nft_temp_account: &'a AccountInfo<'b>,
+    pub exhibitor_ft_receiving_account: &'a AccountInfo<'b>,
+    pub ft_mint_account: &'a AccountInfo<'b>,
+    pub escrow_account: &'a AccountInfo<'b>,
+    pub rent: Rent,
+    pub clock: Clock,
+    pub token_program: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    /// Mint of the exhibiting NFT
+    pub nft_mint: Pubkey,
+    /// Bump seed of the escrow account
+    pub escrow_bump_seed: u8,
+    /// Bid book account and its bump seed, only for the uniform-price auction
+    pub bid_book: Option<(&'a AccountInfo<'b>, u8)>,
+    /// Vault account and its bump seed, only for the native SOL auction
+    pub vault: Option<(&'a AccountInfo<'b>, u8)>,
+    /// Fee config of the auction currency, or the one of the auction house it's exhibited in
+    pub fee_config: FeeConfig,
+    /// Creator royalties of the exhibiting NFT
+    pub royalty: Royalty,
+    /// Auction house the auction is exhibited in, or the default pubkey outside of any house
+    pub auction_house: Pubkey,
+    /// NFTs exhibited in the lot in addition to the exhibiting NFT
+    pub bundle: Vec<BundleItemAccounts<'a, 'b>>,
+}
+
+impl<'a, 'b> ExhibitAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        kind: AuctionKind,
+        native: bool,
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Make sure the seller is signed with the client's private key
+        // In the blockchain, the public key is known by solscan etc., so it is necessary to confirm that the call is correctly signed to prevent spoofing.
+        let exhibitor_account = next_signer_account(account_info_iter)?;
+
+        let exhibitor_nft_account = next_account_info(account_info_iter)?;
+        let nft_mint =
+            check_token_account_owner(exhibitor_nft_account, exhibitor_account.key)?.mint;
+
+        // The buyer of the reverse auction escrows the budget FT instead of the NFT.
+        let exhibitor_nft_temp_account = next_account_info(account_info_iter)?;
+        if kind == AuctionKind::Reverse {
+            check_ft_account(exhibitor_nft_temp_account, exhibitor_account.key, &nft_mint)?;
+        } else {
+            check_nft_account(exhibitor_nft_temp_account, exhibitor_account.key, &nft_mint)?;
+        }
+
+        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        // The bids of the native SOL auction are paid in lamports held in the vault account given instead of the FT mint.
+        let ft_mint_account = next_account_info(account_info_iter)?;
+        if !native {
+            if *ft_mint_account.owner != spl_token::id() {
+                return Err(AuctionError::InvalidMint.into());
+            }
+            Mint::unpack(&ft_mint_account.try_borrow_data()?)
+                .map_err(|_| AuctionError::InvalidMint)?;
+            // The wrapped SOL can be paid out to the exhibitor's wallet by unwrapping it.
+            if !is_wallet(exhibitor_ft_receiving_account, ft_mint_account.key) {
+                check_ft_account(
+                    exhibitor_ft_receiving_account,
+                    exhibitor_account.key,
+                    ft_mint_account.key,
+                )?;
+            }
+        }
+
+        // The escrow account is checked after the auction house is known.
+        let escrow_account = next_account_info(account_info_iter)?;
+
+        let rent = next_rent(account_info_iter)?;
+        let clock = next_clock(account_info_iter)?;
+        let token_program = next_token_program(account_info_iter)?;
+        let system_program = next_system_program(account_info_iter)?;
+
+        // The vault account must be the PDA derived from the escrow account.
+        let vault = if native {
+            let (vault_pda, vault_bump_seed) =
+                Auction::find_vault_address(escrow_account.key, program_id);
+            check_account(ft_mint_account, &vault_pda)?;
+            Some((ft_mint_account, vault_bump_seed))
+        } else {
+            None
+        };
+
+        // The bid book account must be the PDA derived from the escrow account.
+        let bid_book = if kind == AuctionKind::UniformPrice {
+            let bid_book_account = next_account_info(account_info_iter)?;
+            let (bid_book_pda, bid_book_bump_seed) =
+                BidBook::find_address(escrow_account.key, program_id);
+            if bid_book_pda != *bid_book_account.key {
+                return Err(AuctionError::InvalidBidAccount.into());
+            }
+            Some((bid_book_account, bid_book_bump_seed))
+        } else {
+            None
+        };
+
+        // The fee config account must be the PDA derived from the auction currency, and the currency can't be used until the marketplace configures it.
+        // The reverse auction is priced in the budget, and the lamports are charged as the wrapped SOL.
+        let currency_mint = if kind == AuctionKind::Reverse {
+            nft_mint
+        } else if native {
+            spl_token::native_mint::id()
+        } else {
+            *ft_mint_account.key
+        };
+        // The auction exhibited in an auction house is charged the fee of the house instead, and must be priced in a currency allowed in it.
+        let fee_config_account = next_account_info(account_info_iter)?;
+        let (fee_config_pda, _) = FeeConfig::find_address(&currency_mint, program_id);
+        let (auction_house, fee_config) = if fee_config_pda == *fee_config_account.key {
+            if fee_config_account.owner != program_id {
+                return Err(AuctionError::InvalidFeeConfig.into());
+            }
+            let fee_config = FeeConfig::unpack(&fee_config_account.try_borrow_data()?)?;
+            (Pubkey::default(), fee_config)
+        } else {
+            let house = unpack_auction_house(fee_config_account, program_id)?;
+            let currency = house
+                .currency(&currency_mint)
+                .ok_or(AuctionError::CurrencyNotAllowed)?;
+            let fee_config = FeeConfig {
+                is_initialized: true,
+                ft_mint_pubkey: currency_mint,
+                treasury_pubkey: currency.treasury_pubkey,
+                fee_bps: house.fee_bps,
+                referral_bps: house.referral_bps,
+                referral_from_fee: house.referral_from_fee,
+            };
+            (*fee_config_account.key, fee_config)
+        };
+
+        // The royalties are fixed at the time of exhibiting, so that the creators can't raise them after the bids are placed.
+        // The reverse auction is priced in the budget and the seller's token is not an NFT, so no royalty is paid.
+        let royalty = if kind == AuctionKind::Reverse {
+            Royalty {
+                seller_fee_basis_points: 0,
+                creators: Vec::new(),
+            }
+        } else {
+            next_metadata(account_info_iter, &nft_mint)?
+        };
+
+        // The escrow account must be the PDA derived from the auction house, the exhibitor and the NFT mint.
+        let (escrow_pda, escrow_bump_seed) = Auction::find_escrow_address(
+            &auction_house,
+            exhibitor_account.key,
+            &nft_mint,
+            program_id,
+        );
+        if escrow_pda != *escrow_account.key {
+            return Err(AuctionError::InvalidEscrowAccount.into());
+        }
+
+        // The rest of the accounts are the NFTs exhibited in the lot in addition to the exhibiting NFT.
+        // The units of the uniform-price auction must be of the same mint, so they can't be exhibited with it.
+        let mut bundle = Vec::new();
+        while let Some(nft_temp_account) = account_info_iter.next() {
+            let nft_account = next_account_info(account_info_iter)?;
+            let nft_mint = check_token_account_owner(nft_account, exhibitor_account.key)?.mint;
+            check_nft_account(nft_temp_account, exhibitor_account.key, &nft_mint)?;
+            bundle.push(BundleItemAccounts {
+                nft_temp_account,
+                nft_account,
+            });
+        }
+        if bundle.len() > MAX_BUNDLE_ITEMS
+            || (kind == AuctionKind::UniformPrice && !bundle.is_empty())
+        {
+            return Err(AuctionError::InvalidBundle.into());
+        }
+
+        Ok(ExhibitAccounts {
+            exhibitor_account,
+            exhibitor_nft_account,
+            exhibitor_nft_temp_account,
+            exhibitor_ft_receiving_account,
+            ft_mint_account,
+            escrow_account,
+            rent,
+            clock,
+            token_program,
+            system_program,
+            nft_mint,
+            escrow_bump_seed,
+            bid_book,
+            vault,
+            fee_config,
+            royalty,
+            auction_house,
+            bundle,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::Bid`
+pub struct BidAccounts<'a, 'b> {
+    pub bidder_account: &'a AccountInfo<'b>,
+    pub highest_bidder_account: &'a AccountInfo<'b>,
+    pub highest_bidder_ft_temp_account: &'a AccountInfo<'b>,
+    pub highest_bidder_ft_returning_account: &'a AccountInfo<'b>,
+    pub bidder_ft_temp_account: &'a AccountInfo<'b>,
+    pub bidder_ft_account: &'a AccountInfo<'b>,
+    pub bidder_nft_receiving_account: &'a AccountInfo<'b>,
+    pub escrow_account: &'a AccountInfo<'b>,
+    pub clock: Clock,
+    pub token_program: &'a AccountInfo<'b>,
+    pub pda_account: &'a AccountInfo<'b>,
+    pub auction_info: Auction,
+    /// Bid book account and the bid book held by it, only for the uniform-price auction
+    pub bid_book: Option<(&'a AccountInfo<'b>, BidBook)>,
+    /// System program to deposit the lamports, only for the native SOL auction or when wrapping the lamports
+    pub system_program: Option<&'a AccountInfo<'b>>,
+    /// Mint of the wrapped SOL and bump seed of the bidder's wSOL account, only when wrapping the lamports
+    pub wsol: Option<(&'a AccountInfo<'b>, u8)>,
+    /// Referrer's FT account to receive the referral fee, only when the bidder was referred
+    pub referrer_account: Option<&'a AccountInfo<'b>>,
+}
+
+impl<'a, 'b> BidAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Make sure the bidder is signed with their private key
+        let bidder_account = next_signer_account(account_info_iter)?;
+        let highest_bidder_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_returning_account = next_account_info(account_info_iter)?;
+        let bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let bidder_ft_account = next_account_info(account_info_iter)?;
+        let bidder_nft_receiving_account = next_account_info(account_info_iter)?;
+        let (escrow_account, auction_info) = next_escrow_account(account_info_iter, program_id)?;
+        let clock = next_clock(account_info_iter)?;
+        let token_program = next_token_program(account_info_iter)?;
+        let pda_account = next_pda_account(
+            account_info_iter,
+            escrow_account.key,
+            auction_info.bump_seed,
+            program_id,
+        )?;
+
+        let bid_book =
+            next_bid_book_account(account_info_iter, escrow_account, &auction_info, program_id)?;
+        // The bidder on the auction priced in wrapped SOL can bid in lamports from their wallet,
+        // which are wrapped into the wSOL account of the PDA derived from the escrow account and the bidder.
+        let wraps = is_wallet(bidder_ft_account, &auction_info.ft_mint_pubkey);
+        let system_program = if auction_info.is_native() || wraps {
+            Some(next_system_program(account_info_iter)?)
+        } else {
+            None
+        };
+        let wsol = if wraps {
+            let native_mint_account = next_account_info(account_info_iter)?;
+            check_account(native_mint_account, &spl_token::native_mint::id())?;
+            check_account(bidder_ft_account, bidder_account.key)?;
+            let (wsol_pda, wsol_bump_seed) =
+                Auction::find_wsol_address(escrow_account.key, bidder_account.key, program_id);
+            check_account(bidder_ft_temp_account, &wsol_pda)?;
+            Some((native_mint_account, wsol_bump_seed))
+        } else {
+            None
+        };
+        // The referrer is paid in the auction currency at settlement, so the account must hold it.
+        // Only the single successful bidder of the auction can be referred.
+        let referrer_account = account_info_iter.next();
+        if let Some(referrer_account) = referrer_account {
+            if bid_book.is_some() {
+                return Err(AuctionError::InvalidAuctionKind.into());
+            }
+            check_ft_mint(referrer_account, &auction_info.currency_mint())?;
+        }
+
+        // Check if the data held in the account matches the information passed by the client.
+        // The account used must be included in the Instruction argument 'accounts', as it is not possible to retrieve account information from within the Instruction using the account's public key.
+        // The bidder to be outbid is the highest bidder, or the lowest bidder in the bid book of the uniform-price auction when it's full.
+        let outbid_bidder = match &bid_book {
+            Some((_, bid_book)) => bid_book.lowest_if_full(auction_info.quantity).map(|entry| {
+                (
+                    entry.bidder_pubkey,
+                    entry.bidder_ft_temp_pubkey,
+                    entry.bidder_ft_returning_pubkey,
+                )
+            }),
+            None => Some((
+                auction_info.highest_bidder_pubkey,
+                auction_info.highest_bidder_ft_temp_pubkey,
+                auction_info.highest_bidder_ft_returning_pubkey,
+            )),
+        };
+        if let Some((bidder, ft_temp, ft_returning)) = outbid_bidder {
+            check_account(highest_bidder_account, &bidder)?;
+            check_account(highest_bidder_ft_temp_account, &ft_temp)?;
+            check_account(highest_bidder_ft_returning_account, &ft_returning)?;
+        }
+
+        // The NFT will be sent to this account even if the auction is settled by someone other than the bidder.
+        check_lot_account(
+            bidder_nft_receiving_account,
+            bidder_account.key,
+            &auction_info,
+        )?;
+        // The lamports of the native SOL auction are deposited in the vault and returned to the bidder's wallet.
+        if auction_info.is_native() {
+            check_account(bidder_ft_temp_account, &auction_info.vault_pubkey)?;
+            check_account(bidder_ft_account, bidder_account.key)?;
+        } else if wsol.is_none() {
+            // The FT deposited and returned must be the one accepted by the exhibitor.
+            check_ft_account(
+                bidder_ft_temp_account,
+                bidder_account.key,
+                &auction_info.ft_mint_pubkey,
+            )?;
+            check_ft_account(
+                bidder_ft_account,
+                bidder_account.key,
+                &auction_info.ft_mint_pubkey,
+            )?;
+        }
+        if !auction_info.is_native()
+            && outbid_bidder.is_some_and(|(bidder, _, _)| bidder != Pubkey::default())
+        {
+            check_ft_account(
+                highest_bidder_ft_temp_account,
+                pda_account.key,
+                &auction_info.ft_mint_pubkey,
+            )?;
+            check_ft_receiving_account(
+                highest_bidder_ft_returning_account,
+                &auction_info.ft_mint_pubkey,
+            )?;
+        }
+
+        Ok(BidAccounts {
+            bidder_account,
+            highest_bidder_account,
+            highest_bidder_ft_temp_account,
+            highest_bidder_ft_returning_account,
+            bidder_ft_temp_account,
+            bidder_ft_account,
+            bidder_nft_receiving_account,
+            escrow_account,
+            clock,
+            token_program,
+            pda_account,
+            auction_info,
+            bid_book,
+            system_program,
+            wsol,
+            referrer_account,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::Cancel`
+pub struct CancelAccounts<'a, 'b> {
+    pub exhibitor_account: &'a AccountInfo<'b>,
+    pub exhibiting_nft_temp_account: &'a AccountInfo<'b>,
+    pub exhibiting_nft_returning_account: &'a AccountInfo<'b>,
+    pub escrow_account: &'a AccountInfo<'b>,
+    pub token_program: &'a AccountInfo<'b>,
+    pub pda_account: &'a AccountInfo<'b>,
+    pub auction_info: Auction,
+    /// Bid book account and the bid book held by it, only for the uniform-price auction
+    pub bid_book: Option<(&'a AccountInfo<'b>, BidBook)>,
+    /// Vault account, only for the native SOL auction
+    pub vault_account: Option<&'a AccountInfo<'b>>,
+    /// NFTs in the lot in addition to the exhibiting NFT
+    pub bundle: Vec<BundleItemAccounts<'a, 'b>>,
+}
+
+impl<'a, 'b> CancelAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Check if it is signed with the seller's private key
+        let exhibitor_account = next_signer_account(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let exhibiting_nft_returning_account = next_account_info(account_info_iter)?;
+        let (escrow_account, auction_info) = next_escrow_account(account_info_iter, program_id)?;
+        let token_program = next_token_program(account_info_iter)?;
+        let pda_account = next_pda_account(
+            account_info_iter,
+            escrow_account.key,
+            auction_info.bump_seed,
+            program_id,
+        )?;
+
+        let bid_book =
+            next_bid_book_account(account_info_iter, escrow_account, &auction_info, program_id)?;
+
+        let vault_account = next_vault_account(account_info_iter, &auction_info)?;
+        let bundle = next_bundle_accounts(account_info_iter, &auction_info, pda_account.key)?;
+
+        // Allow only exhibitor to cancel
+        check_account(exhibitor_account, &auction_info.exhibitor_pubkey)?;
+        check_account(
+            exhibiting_nft_temp_account,
+            &auction_info.exhibiting_nft_temp_pubkey,
+        )?;
+        check_lot_account(exhibiting_nft_temp_account, pda_account.key, &auction_info)?;
+        check_lot_account(
+            exhibiting_nft_returning_account,
+            exhibitor_account.key,
+            &auction_info,
+        )?;
+
+        Ok(CancelAccounts {
+            exhibitor_account,
+            exhibiting_nft_temp_account,
+            exhibiting_nft_returning_account,
+            escrow_account,
+            token_program,
+            pda_account,
+            auction_info,
+            bid_book,
+            vault_account,
+            bundle,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::Reclaim`
+pub struct ReclaimAccounts<'a, 'b> {
+    pub exhibitor_account: &'a AccountInfo<'b>,
+    pub exhibiting_nft_temp_account: &'a AccountInfo<'b>,
+    pub exhibitor_nft_account: &'a AccountInfo<'b>,
+    pub escrow_account: &'a AccountInfo<'b>,
+    pub clock: Clock,
+    pub token_program: &'a AccountInfo<'b>,
+    pub pda_account: &'a AccountInfo<'b>,
+    pub auction_info: Auction,
+    /// Bid book account and the bid book held by it, only for the uniform-price auction
+    pub bid_book: Option<(&'a AccountInfo<'b>, BidBook)>,
+    /// Vault account, only for the native SOL auction
+    pub vault_account: Option<&'a AccountInfo<'b>>,
+    /// NFTs in the lot in addition to the exhibiting NFT
+    pub bundle: Vec<BundleItemAccounts<'a, 'b>>,
+}
+
+impl<'a, 'b> ReclaimAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Anyone can reclaim, so no signature is required and every account must be the one saved in the auction data.
+        let exhibitor_account = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let exhibitor_nft_account = next_account_info(account_info_iter)?;
+        let (escrow_account, auction_info) = next_escrow_account(account_info_iter, program_id)?;
+        let clock = next_clock(account_info_iter)?;
+        let token_program = next_token_program(account_info_iter)?;
+        let pda_account = next_pda_account(
+            account_info_iter,
+            escrow_account.key,
+            auction_info.bump_seed,
+            program_id,
+        )?;
+
+        let bid_book =
+            next_bid_book_account(account_info_iter, escrow_account, &auction_info, program_id)?;
+
+        let vault_account = next_vault_account(account_info_iter, &auction_info)?;
+        let bundle = next_bundle_accounts(account_info_iter, &auction_info, pda_account.key)?;
+
+        check_account(exhibitor_account, &auction_info.exhibitor_pubkey)?;
+        check_account(
+            exhibiting_nft_temp_account,
+            &auction_info.exhibiting_nft_temp_pubkey,
+        )?;
+        check_account(exhibitor_nft_account, &auction_info.exhibitor_nft_pubkey)?;
+        check_lot_account(exhibiting_nft_temp_account, pda_account.key, &auction_info)?;
+        check_lot_account(exhibitor_nft_account, exhibitor_account.key, &auction_info)?;
+
+        Ok(ReclaimAccounts {
+            exhibitor_account,
+            exhibiting_nft_temp_account,
+            exhibitor_nft_account,
+            escrow_account,
+            clock,
+            token_program,
+            pda_account,
+            auction_info,
+            bid_book,
+            vault_account,
+            bundle,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::Buy`
+pub struct BuyAccounts<'a, 'b> {
+    pub buyer_account: &'a AccountInfo<'b>,
+    pub buyer_ft_account: &'a AccountInfo<'b>,
+    pub buyer_nft_receiving_account: &'a AccountInfo<'b>,
+    pub exhibitor_account: &'a AccountInfo<'b>,
+    pub exhibitor_ft_receiving_account: &'a AccountInfo<'b>,
+    pub exhibiting_nft_temp_account: &'a AccountInfo<'b>,
+    pub escrow_account: &'a AccountInfo<'b>,
+    pub clock: Clock,
+    pub token_program: &'a AccountInfo<'b>,
+    pub pda_account: &'a AccountInfo<'b>,
+    pub auction_info: Auction,
+    /// System program to pay the lamports, only for the native SOL auction
+    pub system_program: Option<&'a AccountInfo<'b>>,
+    /// Vault account, only for the native SOL auction
+    pub vault_account: Option<&'a AccountInfo<'b>>,
+    /// Treasury account to receive the marketplace fee, only when the fee is charged
+    pub treasury_account: Option<&'a AccountInfo<'b>>,
+    /// Creator royalties of the exhibiting NFT
+    pub royalty: RoyaltyAccounts<'a, 'b>,
+    /// NFTs in the lot in addition to the exhibiting NFT
+    pub bundle: Vec<BundleItemAccounts<'a, 'b>>,
+}
+
+/// Accounts of the current highest bidder to give back the deposited FT to
+pub struct HighestBidderAccounts<'a, 'b> {
+    pub highest_bidder_account: &'a AccountInfo<'b>,
+    pub highest_bidder_ft_temp_account: &'a AccountInfo<'b>,
+    pub highest_bidder_ft_returning_account: &'a AccountInfo<'b>,
+}
+
+/// Accounts of `AuctionInstruction::BuyNow`
+pub struct BuyNowAccounts<'a, 'b> {
+    pub buy: BuyAccounts<'a, 'b>,
+    pub highest_bidder: HighestBidderAccounts<'a, 'b>,
+}
+
+impl<'a, 'b> BuyAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let (buy, _) = Self::parse_with(program_id, accounts, false)?;
+        Ok(buy)
+    }
+
+    /// Parses the accounts shared by `AuctionInstruction::Buy` and `AuctionInstruction::BuyNow`.
+    /// Only the latter has the current highest bidder's accounts, right after the exhibiting NFT account,
+    /// since the Dutch auction never has any bidder.
+    fn parse_with(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        has_highest_bidder: bool,
+    ) -> Result<(Self, Option<HighestBidderAccounts<'a, 'b>>), ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Make sure the buyer is signed with their private key, since the FT is paid from the buyer's account.
+        let buyer_account = next_signer_account(account_info_iter)?;
+        let buyer_ft_account = next_account_info(account_info_iter)?;
+        let buyer_nft_receiving_account = next_account_info(account_info_iter)?;
+        let exhibitor_account = next_account_info(account_info_iter)?;
+        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let highest_bidder = if has_highest_bidder {
+            Some(HighestBidderAccounts {
+                highest_bidder_account: next_account_info(account_info_iter)?,
+                highest_bidder_ft_temp_account: next_account_info(account_info_iter)?,
+                highest_bidder_ft_returning_account: next_account_info(account_info_iter)?,
+            })
+        } else {
+            None
+        };
+        let (escrow_account, auction_info) = next_escrow_account(account_info_iter, program_id)?;
+        let clock = next_clock(account_info_iter)?;
+        let token_program = next_token_program(account_info_iter)?;
+        let pda_account = next_pda_account(
+            account_info_iter,
+            escrow_account.key,
+            auction_info.bump_seed,
+            program_id,
+        )?;
+
+        let (system_program, vault_account) = if auction_info.is_native() {
+            (
+                Some(next_system_program(account_info_iter)?),
+                next_vault_account(account_info_iter, &auction_info)?,
+            )
+        } else {
+            (None, None)
+        };
+        let treasury_account = next_treasury_account(account_info_iter, &auction_info)?;
+        let royalty = next_royalty_accounts(account_info_iter, &auction_info)?;
+        let bundle = next_bundle_accounts(account_info_iter, &auction_info, pda_account.key)?;
+
+        check_account(exhibitor_account, &auction_info.exhibitor_pubkey)?;
+        check_account(
+            exhibitor_ft_receiving_account,
+            &auction_info.exhibitor_ft_receiving_pubkey,
+        )?;
+        check_account(
+            exhibiting_nft_temp_account,
+            &auction_info.exhibiting_nft_temp_pubkey,
+        )?;
+
+        check_nft_account(
+            buyer_nft_receiving_account,
+            buyer_account.key,
+            &auction_info.nft_mint_pubkey,
+        )?;
+        check_nft_account(
+            exhibiting_nft_temp_account,
+            pda_account.key,
+            &auction_info.nft_mint_pubkey,
+        )?;
+        // The buyer of the native SOL auction pays the lamports from their wallet.
+        if auction_info.is_native() {
+            check_account(buyer_ft_account, buyer_account.key)?;
+        } else {
+            check_ft_account(
+                buyer_ft_account,
+                buyer_account.key,
+                &auction_info.ft_mint_pubkey,
+            )?;
+            check_ft_mint(exhibitor_ft_receiving_account, &auction_info.ft_mint_pubkey)?;
+        }
+
+        if let Some(highest_bidder) = &highest_bidder {
+            check_account(
+                highest_bidder.highest_bidder_account,
+                &auction_info.highest_bidder_pubkey,
+            )?;
+            check_account(
+                highest_bidder.highest_bidder_ft_temp_account,
+                &auction_info.highest_bidder_ft_temp_pubkey,
+            )?;
+            check_account(
+                highest_bidder.highest_bidder_ft_returning_account,
+                &auction_info.highest_bidder_ft_returning_pubkey,
+            )?;
+            if !auction_info.is_native() && auction_info.highest_bidder_pubkey != Pubkey::default()
+            {
+                check_ft_account(
+                    highest_bidder.highest_bidder_ft_temp_account,
+                    pda_account.key,
+                    &auction_info.ft_mint_pubkey,
+                )?;
+                check_ft_receiving_account(
+                    highest_bidder.highest_bidder_ft_returning_account,
+                    &auction_info.ft_mint_pubkey,
+                )?;
+            }
+        }
+
+        Ok((
+            BuyAccounts {
+                buyer_account,
+                buyer_ft_account,
+                buyer_nft_receiving_account,
+                exhibitor_account,
+                exhibitor_ft_receiving_account,
+                exhibiting_nft_temp_account,
+                escrow_account,
+                clock,
+                token_program,
+                pda_account,
+                auction_info,
+                system_program,
+                vault_account,
+                treasury_account,
+                royalty,
+                bundle,
+            },
+            highest_bidder,
+        ))
+    }
+}
+
+impl<'a, 'b> BuyNowAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let (buy, highest_bidder) = BuyAccounts::parse_with(program_id, accounts, true)?;
+        let highest_bidder = highest_bidder.ok_or(ProgramError::NotEnoughAccountKeys)?;
+        Ok(BuyNowAccounts {
+            buy,
+            highest_bidder,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::Close` and `AuctionInstruction::Settle`
+pub struct CloseAccounts<'a, 'b> {
+    pub highest_bidder_account: &'a AccountInfo<'b>,
+    pub exhibitor_account: &'a AccountInfo<'b>,
+    pub exhibiting_nft_temp_account: &'a AccountInfo<'b>,
+    pub exhibitor_ft_receiving_account: &'a AccountInfo<'b>,
+    pub highest_bidder_ft_temp_account: &'a AccountInfo<'b>,
+    pub highest_bidder_nft_receiving_account: &'a AccountInfo<'b>,
+    pub escrow_account: &'a AccountInfo<'b>,
+    pub clock: Clock,
+    pub token_program: &'a AccountInfo<'b>,
+    pub pda_account: &'a AccountInfo<'b>,
+    pub highest_bidder_ft_returning_account: &'a AccountInfo<'b>,
+    pub exhibitor_nft_account: &'a AccountInfo<'b>,
+    /// Sealed bid account of the successful bidder, only for the sealed bid auction
+    pub winning_bid_account: Option<&'a AccountInfo<'b>>,
+    pub auction_info: Auction,
+    /// System program to unwrap the wrapped SOL, only when it's paid out to the exhibitor's wallet
+    pub system_program: Option<&'a AccountInfo<'b>>,
+    /// Treasury account to receive the marketplace fee, only when the fee is charged
+    pub treasury_account: Option<&'a AccountInfo<'b>>,
+    /// Creator royalties of the exhibiting NFT, which are not paid in the reverse auction
+    pub royalty: RoyaltyAccounts<'a, 'b>,
+    /// Referrer's FT account to receive the referral fee, only when the successful bidder was referred and the fee is charged
+    pub referrer_account: Option<&'a AccountInfo<'b>>,
+    /// NFTs in the lot in addition to the exhibiting NFT
+    pub bundle: Vec<BundleItemAccounts<'a, 'b>>,
+}
+
+impl<'a, 'b> CloseAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        Self::parse_with(program_id, accounts, false)
+    }
+
+    /// Parses the accounts of `AuctionInstruction::Settle`, which doesn't require any signature
+    pub fn parse_settle(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        Self::parse_with(program_id, accounts, true)
+    }
+
+    fn parse_with(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+        is_settle: bool,
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // When the winning bidder closes the auction, they can choose the token account for receiving the NFT,
+        // so make sure that it is signed with the winning bidder's private key.
+        // Anyone can settle the auction instead, but then the NFT is sent only to the account specified at the time of bidding.
+        let highest_bidder_account = if is_settle {
+            next_account_info(account_info_iter)?
+        } else {
+            next_signer_account(account_info_iter)?
+        };
+        let exhibitor_account = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        let highest_bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let highest_bidder_nft_receiving_account = next_account_info(account_info_iter)?;
+        let (escrow_account, auction_info) = next_escrow_account(account_info_iter, program_id)?;
+        let clock = next_clock(account_info_iter)?;
+        let token_program = next_token_program(account_info_iter)?;
+        let pda_account = next_pda_account(
+            account_info_iter,
+            escrow_account.key,
+            auction_info.bump_seed,
+            program_id,
+        )?;
+        // These accounts are used only when the reserve price is not met.
+        let highest_bidder_ft_returning_account = next_account_info(account_info_iter)?;
+        let exhibitor_nft_account = next_account_info(account_info_iter)?;
+
+        // Only the highest bidder can close auction.
+        if auction_info.highest_bidder_pubkey == Pubkey::default() {
+            return Err(AuctionError::NoBidderFound.into());
+        }
+        // The sealed bid account of the successful bidder is closed with the auction.
+        let winning_bid_account = if auction_info.is_sealed() {
+            let (bid_account, bid_info) = next_bid_account(account_info_iter, program_id)?;
+            check_account(escrow_account, &bid_info.escrow_pubkey)?;
+            check_account(highest_bidder_account, &bid_info.bidder_pubkey)?;
+            Some(bid_account)
+        } else {
+            None
+        };
+        // The wrapped SOL paid out to the exhibitor's wallet is unwrapped with the system program.
+        let system_program =
+            if is_wallet(exhibitor_ft_receiving_account, &auction_info.ft_mint_pubkey) {
+                Some(next_system_program(account_info_iter)?)
+            } else {
+                None
+            };
+        let treasury_account = next_treasury_account(account_info_iter, &auction_info)?;
+        let royalty = next_royalty_accounts(account_info_iter, &auction_info)?;
+        let referrer_account = next_referrer_account(account_info_iter, &auction_info)?;
+        // The NFTs in the lot follow the sealed bid account, the system program, the treasury account, the royalty accounts and the referrer account.
+        let bundle = next_bundle_accounts(account_info_iter, &auction_info, pda_account.key)?;
+        check_account(highest_bidder_account, &auction_info.highest_bidder_pubkey)?;
+        if is_settle {
+            check_account(
+                highest_bidder_nft_receiving_account,
+                &auction_info.highest_bidder_nft_receiving_pubkey,
+            )?;
+        }
+        check_account(exhibitor_account, &auction_info.exhibitor_pubkey)?;
+        check_account(
+            highest_bidder_ft_returning_account,
+            &auction_info.highest_bidder_ft_returning_pubkey,
+        )?;
+        check_account(exhibitor_nft_account, &auction_info.exhibitor_nft_pubkey)?;
+        check_account(
+            exhibiting_nft_temp_account,
+            &auction_info.exhibiting_nft_temp_pubkey,
+        )?;
+        check_account(
+            exhibitor_ft_receiving_account,
+            &auction_info.exhibitor_ft_receiving_pubkey,
+        )?;
+        check_account(
+            highest_bidder_ft_temp_account,
+            &auction_info.highest_bidder_ft_temp_pubkey,
+        )?;
+
+        check_lot_account(exhibiting_nft_temp_account, pda_account.key, &auction_info)?;
+        check_lot_account(
+            highest_bidder_nft_receiving_account,
+            highest_bidder_account.key,
+            &auction_info,
+        )?;
+        // The temporary FT account of the native SOL auction is the vault account, which is the one saved in the auction data.
+        if !auction_info.is_native() {
+            check_ft_receiving_account(
+                exhibitor_ft_receiving_account,
+                &auction_info.ft_mint_pubkey,
+            )?;
+            check_ft_account(
+                highest_bidder_ft_temp_account,
+                pda_account.key,
+                &auction_info.ft_mint_pubkey,
+            )?;
+        }
+
+        Ok(CloseAccounts {
+            highest_bidder_account,
+            exhibitor_account,
+            exhibiting_nft_temp_account,
+            exhibitor_ft_receiving_account,
+            highest_bidder_ft_temp_account,
+            highest_bidder_nft_receiving_account,
+            escrow_account,
+            clock,
+            token_program,
+            pda_account,
+            highest_bidder_ft_returning_account,
+            exhibitor_nft_account,
+            winning_bid_account,
+            auction_info,
+            system_program,
+            treasury_account,
+            royalty,
+            referrer_account,
+            bundle,
+        })
+    }
+}
+
+/// Accounts of an NFT exhibited in the lot in addition to the exhibiting NFT
+pub struct BundleItemAccounts<'a, 'b> {
+    pub nft_temp_account: &'a AccountInfo<'b>,
+    /// NFT account to send the NFT from when exhibiting, otherwise to receive it
+    pub nft_account: &'a AccountInfo<'b>,
+}
+
+/// Creator royalties of the exhibiting NFT and the accounts of the verified creators to receive them
+pub struct RoyaltyAccounts<'a, 'b> {
+    /// Royalties in basis points of the price, which is zero when the NFT has no metadata
+    pub seller_fee_basis_points: u16,
+    /// FT account of each verified creator and their share in percent of the royalties
+    pub creators: Vec<(&'a AccountInfo<'b>, u8)>,
+}
+
+/// Accounts of a bidder in the bid book of the uniform-price auction
+pub struct BidEntryAccounts<'a, 'b> {
+    pub bidder_account: &'a AccountInfo<'b>,
+    pub bidder_ft_temp_account: &'a AccountInfo<'b>,
+    pub bidder_ft_returning_account: &'a AccountInfo<'b>,
+    pub bidder_nft_receiving_account: &'a AccountInfo<'b>,
+}
+
+/// Accounts of `AuctionInstruction::SettleUniform`
+pub struct SettleUniformAccounts<'a, 'b> {
+    pub exhibitor_account: &'a AccountInfo<'b>,
+    pub exhibiting_nft_temp_account: &'a AccountInfo<'b>,
+    pub exhibitor_ft_receiving_account: &'a AccountInfo<'b>,
+    pub exhibitor_nft_account: &'a AccountInfo<'b>,
+    pub escrow_account: &'a AccountInfo<'b>,
+    pub bid_book_account: &'a AccountInfo<'b>,
+    pub clock: Clock,
+    pub token_program: &'a AccountInfo<'b>,
+    pub pda_account: &'a AccountInfo<'b>,
+    /// Treasury account to receive the marketplace fee, only when the fee is charged
+    pub treasury_account: Option<&'a AccountInfo<'b>>,
+    /// Creator royalties of the units
+    pub royalty: RoyaltyAccounts<'a, 'b>,
+    /// Accounts of each bidder in the order of the bid book
+    pub bid_entries: Vec<BidEntryAccounts<'a, 'b>>,
+    pub auction_info: Auction,
+    pub bid_book: BidBook,
+}
+
+impl<'a, 'b> SettleUniformAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Anyone can settle, so no signature is required and every account must be the one saved in the auction data and the bid book.
+        let exhibitor_account = next_account_info(account_info_iter)?;
+        let exhibiting_nft_temp_account = next_account_info(account_info_iter)?;
+        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        let exhibitor_nft_account = next_account_info(account_info_iter)?;
+        let (escrow_account, auction_info) = next_escrow_account(account_info_iter, program_id)?;
+        let (bid_book_account, bid_book) =
+            next_bid_book_account(account_info_iter, escrow_account, &auction_info, program_id)?
+                .ok_or(AuctionError::InvalidAuctionKind)?;
+        let clock = next_clock(account_info_iter)?;
+        let token_program = next_token_program(account_info_iter)?;
+        let pda_account = next_pda_account(
+            account_info_iter,
+            escrow_account.key,
+            auction_info.bump_seed,
+            program_id,
+        )?;
+
+        check_account(exhibitor_account, &auction_info.exhibitor_pubkey)?;
+        check_account(
+            exhibiting_nft_temp_account,
+            &auction_info.exhibiting_nft_temp_pubkey,
+        )?;
+        check_account(
+            exhibitor_ft_receiving_account,
+            &auction_info.exhibitor_ft_receiving_pubkey,
+        )?;
+        check_account(exhibitor_nft_account, &auction_info.exhibitor_nft_pubkey)?;
+        check_nft_account(
+            exhibiting_nft_temp_account,
+            pda_account.key,
+            &auction_info.nft_mint_pubkey,
+        )?;
+        check_ft_mint(exhibitor_ft_receiving_account, &auction_info.ft_mint_pubkey)?;
+        let treasury_account = next_treasury_account(account_info_iter, &auction_info)?;
+        let royalty = next_royalty_accounts(account_info_iter, &auction_info)?;
+
+        let bid_entries = bid_book
+            .entries
+            .iter()
+            .map(|entry| {
+                let bidder_account = next_account_info(account_info_iter)?;
+                let bidder_ft_temp_account = next_account_info(account_info_iter)?;
+                let bidder_ft_returning_account = next_account_info(account_info_iter)?;
+                let bidder_nft_receiving_account = next_account_info(account_info_iter)?;
+                check_account(bidder_account, &entry.bidder_pubkey)?;
+                check_account(bidder_ft_temp_account, &entry.bidder_ft_temp_pubkey)?;
+                check_account(
+                    bidder_ft_returning_account,
+                    &entry.bidder_ft_returning_pubkey,
+                )?;
+                check_account(
+                    bidder_nft_receiving_account,
+                    &entry.bidder_nft_receiving_pubkey,
+                )?;
+                Ok(BidEntryAccounts {
+                    bidder_account,
+                    bidder_ft_temp_account,
+                    bidder_ft_returning_account,
+                    bidder_nft_receiving_account,
+                })
+            })
+            .collect::<Result<_, ProgramError>>()?;
+
+        Ok(SettleUniformAccounts {
+            exhibitor_account,
+            exhibiting_nft_temp_account,
+            exhibitor_ft_receiving_account,
+            exhibitor_nft_account,
+            escrow_account,
+            bid_book_account,
+            clock,
+            token_program,
+            pda_account,
+            treasury_account,
+            royalty,
+            bid_entries,
+            auction_info,
+            bid_book,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::CommitBid`
+pub struct CommitBidAccounts<'a, 'b> {
+    pub bidder_account: &'a AccountInfo<'b>,
+    pub bid_account: &'a AccountInfo<'b>,
+    pub bidder_ft_temp_account: &'a AccountInfo<'b>,
+    pub bidder_ft_account: &'a AccountInfo<'b>,
+    pub bidder_nft_receiving_account: &'a AccountInfo<'b>,
+    pub escrow_account: &'a AccountInfo<'b>,
+    pub rent: Rent,
+    pub clock: Clock,
+    pub token_program: &'a AccountInfo<'b>,
+    pub system_program: &'a AccountInfo<'b>,
+    pub pda_account: &'a AccountInfo<'b>,
+    pub auction_info: Auction,
+    /// Bump seed of the sealed bid account
+    pub bid_bump_seed: u8,
+}
+
+impl<'a, 'b> CommitBidAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Make sure the bidder is signed with their private key
+        let bidder_account = next_signer_account(account_info_iter)?;
+        let bid_account = next_account_info(account_info_iter)?;
+        let bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let bidder_ft_account = next_account_info(account_info_iter)?;
+        let bidder_nft_receiving_account = next_account_info(account_info_iter)?;
+        let (escrow_account, auction_info) = next_escrow_account(account_info_iter, program_id)?;
+        let rent = next_rent(account_info_iter)?;
+        let clock = next_clock(account_info_iter)?;
+        let token_program = next_token_program(account_info_iter)?;
+        let system_program = next_system_program(account_info_iter)?;
+        let pda_account = next_pda_account(
+            account_info_iter,
+            escrow_account.key,
+            auction_info.bump_seed,
+            program_id,
+        )?;
+
+        // The sealed bid account must be the PDA derived from the escrow account and the bidder,
+        // so that each bidder can commit only once.
+        let (bid_pda, bid_bump_seed) =
+            SealedBid::find_address(escrow_account.key, bidder_account.key, program_id);
+        if bid_pda != *bid_account.key {
+            return Err(AuctionError::InvalidBidAccount.into());
+        }
+
+        check_ft_account(
+            bidder_ft_temp_account,
+            bidder_account.key,
+            &auction_info.ft_mint_pubkey,
+        )?;
+        check_ft_account(
+            bidder_ft_account,
+            bidder_account.key,
+            &auction_info.ft_mint_pubkey,
+        )?;
+        check_nft_account(
+            bidder_nft_receiving_account,
+            bidder_account.key,
+            &auction_info.nft_mint_pubkey,
+        )?;
+
+        Ok(CommitBidAccounts {
+            bidder_account,
+            bid_account,
+            bidder_ft_temp_account,
+            bidder_ft_account,
+            bidder_nft_receiving_account,
+            escrow_account,
+            rent,
+            clock,
+            token_program,
+            system_program,
+            pda_account,
+            auction_info,
+            bid_bump_seed,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::RevealBid`
+pub struct RevealBidAccounts<'a, 'b> {
+    pub bidder_account: &'a AccountInfo<'b>,
+    pub bid_account: &'a AccountInfo<'b>,
+    pub escrow_account: &'a AccountInfo<'b>,
+    pub clock: Clock,
+    pub auction_info: Auction,
+    pub bid_info: SealedBid,
+}
+
+impl<'a, 'b> RevealBidAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Only the bidder knows the salt, but the signature is required so that nobody else can reveal on behalf of them.
+        let bidder_account = next_signer_account(account_info_iter)?;
+        let (bid_account, bid_info) = next_bid_account(account_info_iter, program_id)?;
+        let (escrow_account, auction_info) = next_escrow_account(account_info_iter, program_id)?;
+        let clock = next_clock(account_info_iter)?;
+
+        check_account(bidder_account, &bid_info.bidder_pubkey)?;
+        check_account(escrow_account, &bid_info.escrow_pubkey)?;
+
+        Ok(RevealBidAccounts {
+            bidder_account,
+            bid_account,
+            escrow_account,
+            clock,
+            auction_info,
+            bid_info,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::WithdrawBid`
+pub struct WithdrawBidAccounts<'a, 'b> {
+    pub bidder_account: &'a AccountInfo<'b>,
+    pub bid_account: &'a AccountInfo<'b>,
+    pub bidder_ft_temp_account: &'a AccountInfo<'b>,
+    pub bidder_ft_returning_account: &'a AccountInfo<'b>,
+    pub exhibitor_ft_receiving_account: &'a AccountInfo<'b>,
+    pub clock: Clock,
+    pub token_program: &'a AccountInfo<'b>,
+    pub pda_account: &'a AccountInfo<'b>,
+    /// Auction data, `None` if the escrow account is already closed
+    pub auction_info: Option<Auction>,
+    pub bid_info: SealedBid,
+}
+
+impl<'a, 'b> WithdrawBidAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Anyone can withdraw, so no signature is required and every account must be the one saved in the sealed bid.
+        let bidder_account = next_account_info(account_info_iter)?;
+        let (bid_account, bid_info) = next_bid_account(account_info_iter, program_id)?;
+        let bidder_ft_temp_account = next_account_info(account_info_iter)?;
+        let bidder_ft_returning_account = next_account_info(account_info_iter)?;
+        let exhibitor_ft_receiving_account = next_account_info(account_info_iter)?;
+        let escrow_account = next_account_info(account_info_iter)?;
+        let clock = next_clock(account_info_iter)?;
+        let token_program = next_token_program(account_info_iter)?;
+        let pda_account = next_pda_account(
+            account_info_iter,
+            &bid_info.escrow_pubkey,
+            bid_info.bump_seed,
+            program_id,
+        )?;
+
+        check_account(bidder_account, &bid_info.bidder_pubkey)?;
+        check_account(bidder_ft_temp_account, &bid_info.bidder_ft_temp_pubkey)?;
+        check_account(
+            bidder_ft_returning_account,
+            &bid_info.bidder_ft_returning_pubkey,
+        )?;
+        check_account(
+            exhibitor_ft_receiving_account,
+            &bid_info.exhibitor_ft_receiving_pubkey,
+        )?;
+        check_account(escrow_account, &bid_info.escrow_pubkey)?;
+        check_token_account_owner(bidder_ft_temp_account, pda_account.key)?;
+
+        // The deposits can be withdrawn even after the auction is closed.
+        let auction_info = if escrow_account.owner == program_id && !escrow_account.data_is_empty()
+        {
+            Some(Auction::unpack(&escrow_account.try_borrow_data()?)?)
+        } else {
+            None
+        };
+
+        Ok(WithdrawBidAccounts {
+            bidder_account,
+            bid_account,
+            bidder_ft_temp_account,
+            bidder_ft_returning_account,
+            exhibitor_ft_receiving_account,
+            clock,
+            token_program,
+            pda_account,
+            auction_info,
+            bid_info,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::SetFeeConfig`
+pub struct SetFeeConfigAccounts<'a, 'b> {
+    pub authority_account: &'a AccountInfo<'b>,
+    pub fee_config_account: &'a AccountInfo<'b>,
+    pub ft_mint_account: &'a AccountInfo<'b>,
+    pub treasury_account: &'a AccountInfo<'b>,
+    pub rent: Rent,
+    pub system_program: &'a AccountInfo<'b>,
+    /// Bump seed of the fee config account
+    pub fee_config_bump_seed: u8,
+}
+
+impl<'a, 'b> SetFeeConfigAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Only the admin can configure the fee.
+        let authority_account = next_signer_account(account_info_iter)?;
+
+        // The fee config account must be the PDA derived from the FT mint.
+        let fee_config_account = next_account_info(account_info_iter)?;
+        let ft_mint_account = next_account_info(account_info_iter)?;
+        let (fee_config_pda, fee_config_bump_seed) =
+            FeeConfig::find_address(ft_mint_account.key, program_id);
+        if fee_config_pda != *fee_config_account.key {
+            return Err(AuctionError::InvalidFeeConfig.into());
+        }
+        if *ft_mint_account.owner != spl_token::id() {
+            return Err(AuctionError::InvalidMint.into());
+        }
+        Mint::unpack(&ft_mint_account.try_borrow_data()?).map_err(|_| AuctionError::InvalidMint)?;
+
+        let treasury_account = next_account_info(account_info_iter)?;
+        check_ft_mint(treasury_account, ft_mint_account.key)?;
+
+        let config_account = next_account_info(account_info_iter)?;
+        let config = unpack_program_config(config_account, program_id)?
+            .ok_or(AuctionError::InvalidProgramConfig)?;
+        if config.admin_pubkey != *authority_account.key {
+            return Err(AuctionError::InvalidAuthority.into());
+        }
+
+        let rent = next_rent(account_info_iter)?;
+        let system_program = next_system_program(account_info_iter)?;
+
+        Ok(SetFeeConfigAccounts {
+            authority_account,
+            fee_config_account,
+            ft_mint_account,
+            treasury_account,
+            rent,
+            system_program,
+            fee_config_bump_seed,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::InitializeConfig`
+pub struct InitializeConfigAccounts<'a, 'b> {
+    pub authority_account: &'a AccountInfo<'b>,
+    pub config_account: &'a AccountInfo<'b>,
+    pub rent: Rent,
+    pub system_program: &'a AccountInfo<'b>,
+    /// Bump seed of the program config account
+    pub config_bump_seed: u8,
+}
+
+impl<'a, 'b> InitializeConfigAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // The first admin is the upgrade authority of this program, since no other account is privileged before it.
+        let authority_account = next_signer_account(account_info_iter)?;
+
+        // The program config account must be the PDA derived from the seed.
+        let config_account = next_account_info(account_info_iter)?;
+        let (config_pda, config_bump_seed) = ProgramConfig::find_address(program_id);
+        if config_pda != *config_account.key {
+            return Err(AuctionError::InvalidProgramConfig.into());
+        }
+
+        let program_data_account = next_account_info(account_info_iter)?;
+        check_upgrade_authority(program_data_account, authority_account, program_id)?;
+
+        let rent = next_rent(account_info_iter)?;
+        let system_program = next_system_program(account_info_iter)?;
+
+        Ok(InitializeConfigAccounts {
+            authority_account,
+            config_account,
+            rent,
+            system_program,
+            config_bump_seed,
+        })
+    }
+}
+
+/// Accounts of the admin instructions updating the program config
+pub struct ProgramConfigAccounts<'a, 'b> {
+    /// Current admin, or the pending admin when accepting the transfer
+    pub signer_account: &'a AccountInfo<'b>,
+    pub config_account: &'a AccountInfo<'b>,
+    pub config: ProgramConfig,
+}
+
+impl<'a, 'b> ProgramConfigAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // The authority of the signer is checked by each instruction.
+        let signer_account = next_signer_account(account_info_iter)?;
+        let config_account = next_account_info(account_info_iter)?;
+        let config = unpack_program_config(config_account, program_id)?
+            .ok_or(AuctionError::InvalidProgramConfig)?;
+
+        Ok(ProgramConfigAccounts {
+            signer_account,
+            config_account,
+            config,
+        })
+    }
+}
+
+/// Accounts of `AuctionInstruction::CreateAuctionHouse`
+pub struct CreateAuctionHouseAccounts<'a, 'b> {
+    pub admin_account: &'a AccountInfo<'b>,
+    pub authority_account: &'a AccountInfo<'b>,
+    pub house_account: &'a AccountInfo<'b>,
+    pub rent: Rent,
+    pub system_program: &'a AccountInfo<'b>,
+    /// Bump seed of the auction house account
+    pub house_bump_seed: u8,
+}
+
+impl<'a, 'b> CreateAuctionHouseAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Only the admin can create the auction house, otherwise the sellers could run their own houses without any fee.
+        let admin_account = next_signer_account(account_info_iter)?;
+        let authority_account = next_account_info(account_info_iter)?;
+
+        // The auction house account must be the PDA derived from the authority.
+        let house_account = next_account_info(account_info_iter)?;
+        let (house_pda, house_bump_seed) =
+            AuctionHouse::find_address(authority_account.key, program_id);
+        if house_pda != *house_account.key {
+            return Err(AuctionError::InvalidAuctionHouse.into());
+        }
+
+        let config_account = next_account_info(account_info_iter)?;
+        let config = unpack_program_config(config_account, program_id)?
+            .ok_or(AuctionError::InvalidProgramConfig)?;
+        if config.admin_pubkey != *admin_account.key {
+            return Err(AuctionError::InvalidAuthority.into());
+        }
+
+        let rent = next_rent(account_info_iter)?;
+        let system_program = next_system_program(account_info_iter)?;
+
+        Ok(CreateAuctionHouseAccounts {
+            admin_account,
+            authority_account,
+            house_account,
+            rent,
+            system_program,
+            house_bump_seed,
+        })
+    }
+}
+
+/// Accounts of the instructions updating the auction house
+pub struct AuctionHouseAccounts<'a, 'b> {
+    pub authority_account: &'a AccountInfo<'b>,
+    pub house_account: &'a AccountInfo<'b>,
+    pub house: AuctionHouse,
+    /// Mint of the currency and the treasury account of the house in it, only for `AuctionInstruction::SetHouseCurrency`
+    pub currency: Option<(&'a AccountInfo<'b>, &'a AccountInfo<'b>)>,
+}
+
+impl<'a, 'b> AuctionHouseAccounts<'a, 'b> {
+    pub fn parse(
+        program_id: &Pubkey,
+        accounts: &'a [AccountInfo<'b>],
+    ) -> Result<Self, ProgramError> {
+        let account_info_iter = &mut accounts.iter();
+
+        // Only the authority of the house can update it.
+        let authority_account = next_signer_account(account_info_iter)?;
+        let house_account = next_account_info(account_info_iter)?;
+        let house = unpack_auction_house(house_account, program_id)?;
+        if house.authority_pubkey != *authority_account.key {
+            return Err(AuctionError::InvalidAuthority.into());
+        }
+
+        // The treasury account must hold the currency to receive the fee in it.
+        let currency = match account_info_iter.next() {
+            Some(ft_mint_account) => {
+                if *ft_mint_account.owner != spl_token::id() {
+                    return Err(AuctionError::InvalidMint.into());
+                }
+                Mint::unpack(&ft_mint_account.try_borrow_data()?)
+                    .map_err(|_| AuctionError::InvalidMint)?;
+                let treasury_account = next_account_info(account_info_iter)?;
+                check_ft_mint(treasury_account, ft_mint_account.key)?;
+                Some((ft_mint_account, treasury_account))
+            }
+            None => None,
+        };
+
+        Ok(AuctionHouseAccounts {
+            authority_account,
+            house_account,
+            house,
+            currency,
+        })
+    }
+}
+
+/// Unpacks the auction house after checking the account is the PDA derived from its authority
+fn unpack_auction_house(
+    house_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<AuctionHouse, ProgramError> {
+    if house_account.owner != program_id {
+        return Err(AuctionError::InvalidAuctionHouse.into());
+    }
+    let house = AuctionHouse::unpack(&house_account.try_borrow_data()?)
+        .map_err(|_| AuctionError::InvalidAuctionHouse)?;
+    let house_pda = Pubkey::create_program_address(
+        &[
+            HOUSE_SEED,
+            house.authority_pubkey.as_ref(),
+            &[house.bump_seed],
+        ],
+        program_id,
+    )
+    .map_err(|_| AuctionError::InvalidAuctionHouse)?;
+    if house_pda != *house_account.key {
+        return Err(AuctionError::InvalidAuctionHouse.into());
+    }
+    Ok(house)
+}
+
+/// Splits the program config account given as the last account off the accounts of the instruction, and returns the rest and the config
+/// The config is `None` until the admin initializes it.
+pub fn split_program_config<'a, 'b>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'b>],
+) -> Result<(&'a [AccountInfo<'b>], Option<ProgramConfig>), ProgramError> {
+    let (config_account, accounts) = accounts
+        .split_last()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let config = unpack_program_config(config_account, program_id)?;
+    Ok((accounts, config))
+}
+
+/// Unpacks the program config after checking the account is the PDA, which is `None` if it's not created yet
+fn unpack_program_config(
+    config_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<Option<ProgramConfig>, ProgramError> {
+    let (config_pda, _) = ProgramConfig::find_address(program_id);
+    if config_pda != *config_account.key {
+        return Err(AuctionError::InvalidProgramConfig.into());
+    }
+    if config_account.owner != program_id {
+        return Ok(None);
+    }
+    Ok(Some(ProgramConfig::unpack(
+        &config_account.try_borrow_data()?,
+    )?))
+}
+
+/// Checks the signer is the upgrade authority of this program recorded in its program data account
+fn check_upgrade_authority(
+    program_data_account: &AccountInfo,
+    authority_account: &AccountInfo,
+    program_id: &Pubkey,
+) -> Result<(), ProgramError> {
+    let (program_data_address, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    check_account(program_data_account, &program_data_address)?;
+    if *program_data_account.owner != bpf_loader_upgradeable::id() {
+        return Err(AuctionError::InvalidAuthority.into());
+    }
+    let program_data = limited_deserialize::<UpgradeableLoaderState>(
+        &program_data_account.try_borrow_data()?,
+        PROGRAM_DATA_METADATA_LEN,
+    )
+    .map_err(|_| AuctionError::InvalidAuthority)?;
+    match program_data {
+        UpgradeableLoaderState::ProgramData {
+            upgrade_authority_address: Some(upgrade_authority_address),
+            ..
+        } if upgrade_authority_address == *authority_account.key => Ok(()),
+        _ => Err(AuctionError::InvalidAuthority.into()),
+    }
+}
+
+fn next_signer_account<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+) -> Result<I::Item, ProgramError> {
+    let account = next_account_info(iter)?;
+    if !account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    Ok(account)
+}
+
+/// Returns the escrow account owned by this program and the auction data held by it
+fn next_escrow_account<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    program_id: &Pubkey,
+) -> Result<(I::Item, Auction), ProgramError> {
+    let escrow_account = next_account_info(iter)?;
+    if escrow_account.owner != program_id {
+        return Err(AuctionError::InvalidEscrowAccount.into());
+    }
+    let auction_info = Auction::unpack(&escrow_account.try_borrow_data()?)?;
+    Ok((escrow_account, auction_info))
+}
+
+/// Returns the sealed bid account owned by this program and the sealed bid held by it
+fn next_bid_account<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    program_id: &Pubkey,
+) -> Result<(I::Item, SealedBid), ProgramError> {
+    let bid_account = next_account_info(iter)?;
+    if bid_account.owner != program_id {
+        return Err(AuctionError::InvalidBidAccount.into());
+    }
+    let bid_info = SealedBid::unpack(&bid_account.try_borrow_data()?)?;
+    Ok((bid_account, bid_info))
+}
+
+/// Returns the bid book account of the escrow account and the bid book held by it, only for the uniform-price auction
+fn next_bid_book_account<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    escrow_account: &AccountInfo,
+    auction_info: &Auction,
+    program_id: &Pubkey,
+) -> Result<Option<(I::Item, BidBook)>, ProgramError> {
+    if auction_info.kind != AuctionKind::UniformPrice {
+        return Ok(None);
+    }
+    let bid_book_account = next_account_info(iter)?;
+    if bid_book_account.owner != program_id {
+        return Err(AuctionError::InvalidBidAccount.into());
+    }
+    let bid_book = BidBook::unpack(&bid_book_account.try_borrow_data()?)?;
+    check_account(escrow_account, &bid_book.escrow_pubkey)?;
+    Ok(Some((bid_book_account, bid_book)))
+}
+
+/// Parses the PDA's temporary NFT account and the NFT account to receive it, for each NFT in the lot in addition to the exhibiting NFT
+/// The owner of the receiving account depends on how the auction ends, so it's checked when the NFT is moved.
+fn next_bundle_accounts<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    auction_info: &Auction,
+    pda: &Pubkey,
+) -> Result<Vec<BundleItemAccounts<'a, 'b>>, ProgramError> {
+    auction_info
+        .bundle()
+        .iter()
+        .map(|nft_temp_pubkey| {
+            let nft_temp_account = next_account_info(iter)?;
+            let nft_account = next_account_info(iter)?;
+            check_account(nft_temp_account, nft_temp_pubkey)?;
+            let nft_mint = check_token_account_owner(nft_temp_account, pda)?.mint;
+            if unpack_token_account(nft_account)?.mint != nft_mint {
+                return Err(AuctionError::NftMintMismatch.into());
+            }
+            Ok(BundleItemAccounts {
+                nft_temp_account,
+                nft_account,
+            })
+        })
+        .collect()
+}
+
+/// Returns the vault account of the native SOL auction after checking it is the one saved in the auction data
+fn next_vault_account<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    auction_info: &Auction,
+) -> Result<Option<I::Item>, ProgramError> {
+    if !auction_info.is_native() {
+        return Ok(None);
+    }
+    let vault_account = next_account_info(iter)?;
+    check_account(vault_account, &auction_info.vault_pubkey)?;
+    Ok(Some(vault_account))
+}
+
+/// Returns the treasury account to receive the marketplace fee after checking it is the one saved in the auction data, only when the fee is charged
+/// The fee is left to the exhibitor if the account can't receive it any more, so that the auction can always be settled.
+fn next_treasury_account<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    auction_info: &Auction,
+) -> Result<Option<I::Item>, ProgramError> {
+    if auction_info.fee_bps == 0 {
+        return Ok(None);
+    }
+    let treasury_account = next_account_info(iter)?;
+    check_account(treasury_account, &auction_info.treasury_pubkey)?;
+    Ok(is_payable(treasury_account, &auction_info.currency_mint()).then_some(treasury_account))
+}
+
+/// Reads the royalties from the metadata account of the NFT
+/// The metadata account may not exist, then no royalty is paid.
+fn next_metadata<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    nft_mint: &Pubkey,
+) -> Result<Royalty, ProgramError> {
+    let metadata_account = next_account_info(iter)?;
+    let (metadata_pda, _) = Royalty::find_metadata_address(nft_mint);
+    if metadata_pda != *metadata_account.key {
+        return Err(AuctionError::InvalidMetadata.into());
+    }
+    if *metadata_account.owner != TOKEN_METADATA_PROGRAM_ID {
+        return Ok(Royalty {
+            seller_fee_basis_points: 0,
+            creators: Vec::new(),
+        });
+    }
+    Royalty::unpack_metadata(&metadata_account.try_borrow_data()?, nft_mint)
+        .map_err(|_| AuctionError::InvalidMetadata.into())
+}
+
+/// Parses the FT accounts of the verified creators saved at the time of exhibiting
+fn next_royalty_accounts<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    auction_info: &Auction,
+) -> Result<RoyaltyAccounts<'a, 'b>, ProgramError> {
+    let mut royalty = RoyaltyAccounts {
+        seller_fee_basis_points: auction_info.royalty_bps,
+        creators: Vec::new(),
+    };
+    // Each creator is paid in the auction currency, so the account must hold it.
+    for (creator, share) in auction_info.creators() {
+        let creator_ft_account = next_account_info(iter)?;
+        check_ft_account(creator_ft_account, creator, &auction_info.currency_mint())?;
+        royalty.creators.push((creator_ft_account, share));
+    }
+    Ok(royalty)
+}
+
+/// Returns the referrer's FT account after checking it is the one saved with the highest bid, only when the successful bidder was referred and the referral fee is charged
+/// No referral fee is paid if the account can't receive it any more, so that the auction can always be settled.
+fn next_referrer_account<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    auction_info: &Auction,
+) -> Result<Option<I::Item>, ProgramError> {
+    if auction_info.highest_bidder_referrer_pubkey == Pubkey::default()
+        || auction_info.referral_bps == 0
+    {
+        return Ok(None);
+    }
+    let referrer_account = next_account_info(iter)?;
+    check_account(
+        referrer_account,
+        &auction_info.highest_bidder_referrer_pubkey,
+    )?;
+    Ok(is_payable(referrer_account, &auction_info.currency_mint()).then_some(referrer_account))
+}
+
+/// Returns the PDA account after checking it is derived from the escrow account and the saved bump seed
+fn next_pda_account<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+    escrow: &Pubkey,
+    bump_seed: u8,
+    program_id: &Pubkey,
+) -> Result<I::Item, ProgramError> {
+    let pda_account = next_account_info(iter)?;
+    let bump_seed = [bump_seed];
+    let pda =
+        Pubkey::create_program_address(&Auction::authority_seeds(escrow, &bump_seed), program_id)
+            .map_err(|_| AuctionError::InvalidPdaAccount)?;
+    if pda != *pda_account.key {
+        return Err(AuctionError::InvalidPdaAccount.into());
+    }
+    Ok(pda_account)
+}
+
+fn next_rent<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+) -> Result<Rent, ProgramError> {
+    let sys_var_rent_account = next_account_info(iter)?;
+    if !sysvar::rent::check_id(sys_var_rent_account.key) {
+        return Err(AuctionError::InvalidSysvar.into());
+    }
+    Rent::from_account_info(sys_var_rent_account)
+}
+
+fn next_clock<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+) -> Result<Clock, ProgramError> {
+    let sys_var_clock_account = next_account_info(iter)?;
+    if !sysvar::clock::check_id(sys_var_clock_account.key) {
+        return Err(AuctionError::InvalidSysvar.into());
+    }
+    Clock::from_account_info(sys_var_clock_account)
+}
+
+fn next_token_program<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+) -> Result<I::Item, ProgramError> {
+    let token_program = next_account_info(iter)?;
+    if *token_program.key != spl_token::id() {
+        return Err(AuctionError::InvalidTokenProgram.into());
+    }
+    Ok(token_program)
+}
+
+fn next_system_program<'a, 'b, I: Iterator<Item = &'a AccountInfo<'b>>>(
+    iter: &mut I,
+) -> Result<I::Item, ProgramError> {
+    let system_program = next_account_info(iter)?;
+    if *system_program.key != system_program::id() {
+        return Err(AuctionError::InvalidSystemProgram.into());
+    }
+    Ok(system_program)
+}
+
+/// Checks the account is the one saved in the auction data
+fn check_account(account: &AccountInfo, expected: &Pubkey) -> Result<(), ProgramError> {
+    if account.key != expected {
+        return Err(AuctionError::AccountMismatch.into());
+    }
+    Ok(())
+}
+
+/// Unpacks the token account after checking it is owned by the token program
+fn unpack_token_account(account: &AccountInfo) -> Result<TokenAccount, ProgramError> {
+    if *account.owner != spl_token::id() {
+        return Err(AuctionError::InvalidTokenAccount.into());
+    }
+    TokenAccount::unpack(&account.try_borrow_data()?)
+        .map_err(|_| AuctionError::InvalidTokenAccount.into())
+}
+
+fn check_token_account_owner(
+    account: &AccountInfo,
+    owner: &Pubkey,
+) -> Result<TokenAccount, ProgramError> {
+    let token_account = unpack_token_account(account)?;
+    if token_account.owner != *owner {
+        return Err(AuctionError::TokenAccountOwnerMismatch.into());
+    }
+    Ok(token_account)
+}
+
+/// Whether the account is a token account of the FT mint that is neither closed nor frozen
+fn is_payable(account: &AccountInfo, ft_mint: &Pubkey) -> bool {
+    unpack_token_account(account)
+        .is_ok_and(|token_account| token_account.mint == *ft_mint && !token_account.is_frozen())
+}
+
+/// Whether the account is a wallet to unwrap the wrapped SOL to, rather than a token account
+fn is_wallet(account: &AccountInfo, ft_mint: &Pubkey) -> bool {
+    *ft_mint == spl_token::native_mint::id() && *account.owner == system_program::id()
+}
+
+/// Checks the account can receive the FT, which is a token account of the FT mint or a wallet for the wrapped SOL
+fn check_ft_receiving_account(account: &AccountInfo, ft_mint: &Pubkey) -> Result<(), ProgramError> {
+    if !is_wallet(account, ft_mint) {
+        check_ft_mint(account, ft_mint)?;
+    }
+    Ok(())
+}
+
+fn check_ft_mint(account: &AccountInfo, ft_mint: &Pubkey) -> Result<TokenAccount, ProgramError> {
+    let token_account = unpack_token_account(account)?;
+    if token_account.mint != *ft_mint {
+        return Err(AuctionError::FtMintMismatch.into());
+    }
+    Ok(token_account)
+}
+
+fn check_ft_account(
+    account: &AccountInfo,
+    owner: &Pubkey,
+    ft_mint: &Pubkey,
+) -> Result<TokenAccount, ProgramError> {
+    check_ft_mint(account, ft_mint)?;
+    check_token_account_owner(account, owner)
+}
+
+/// Checks the token account of the exhibited lot, which holds the budget FT instead of the NFT for the reverse auction
+fn check_lot_account(
+    account: &AccountInfo,
+    owner: &Pubkey,
+    auction_info: &Auction,
+) -> Result<TokenAccount, ProgramError> {
+    if auction_info.kind == AuctionKind::Reverse {
+        check_ft_account(account, owner, auction_info.budget_mint())
+    } else {
+        check_nft_account(account, owner, &auction_info.nft_mint_pubkey)
+    }
+}
+
+fn check_nft_account(
+    account: &AccountInfo,
+    owner: &Pubkey,
+    nft_mint: &Pubkey,
+) -> Result<TokenAccount, ProgramError> {
+    let token_account = check_token_account_owner(account, owner)?;
+    if token_account.mint != *nft_mint {
+        return Err(AuctionError::NftMintMismatch.into());
+    }
+    Ok(token_account)
+}
@@ -1,6 +1,7 @@
 import {
   AccountLayout,
   createInitializeAccountInstruction,
+  getOrCreateAssociatedTokenAccount,
   TOKEN_PROGRAM_ID,
 } from "@solana/spl-token";
 import {
@@ -22,6 +23,7 @@ import {
   logAuction,
   getCurrentAuction,
   getConnection,
+  getProgramConfigPubkey,
 } from "./utils";
 
 const bidder = async () => {
@@ -58,6 +60,16 @@ const bidder = async () => {
     TOKEN_PROGRAM_ID
   );
 
+  // The NFT will be sent to this account when the auction is settled.
+  const bidderNftReceivingAccount = await getOrCreateAssociatedTokenAccount(
+    connection,
+    bidderKeypair,
+    new PublicKey(auction.nftMintPubkey),
+    bidderKeypair.publicKey,
+    undefined,
+    undefined
+  );
+
   const highestBidderPubkey = new PublicKey(auction.highestBidderPubkey);
   const highestBidderFtTempPubkey = new PublicKey(
     auction.highestBidderFtTempPubkey
@@ -82,10 +94,20 @@ const bidder = async () => {
         isWritable: true,
       },
       { pubkey: bidderFtAccountPubkey, isSigner: false, isWritable: true },
+      {
+        pubkey: bidderNftReceivingAccount.address,
+        isSigner: false,
+        isWritable: false,
+      },
       { pubkey: escrowAccountPubkey, isSigner: false, isWritable: true },
       { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       { pubkey: pda[0], isSigner: false, isWritable: false },
+      {
+        pubkey: await getProgramConfigPubkey(auctionProgramId),
+        isSigner: false,
+        isWritable: false,
+      },
     ],
     data: Buffer.from(Uint8Array.of(1, ...new BN(price).toArray("le", 8))),
   });
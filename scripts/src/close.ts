@@ -75,6 +75,16 @@ const close = async () => {
       { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
       { pubkey: pda[0], isSigner: false, isWritable: false },
+      {
+        pubkey: new PublicKey(auction.highestBidderFtReturningPubkey),
+        isSigner: false,
+        isWritable: true,
+      },
+      {
+        pubkey: new PublicKey(auction.exhibitorNftPubkey),
+        isSigner: false,
+        isWritable: true,
+      },
     ],
   });
 
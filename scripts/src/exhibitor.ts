@@ -5,6 +5,7 @@ import {
 } from "@solana/spl-token";
 import {
   Keypair,
+  PublicKey,
   SystemProgram,
   SYSVAR_CLOCK_PUBKEY,
   SYSVAR_RENT_PUBKEY,
@@ -18,11 +19,11 @@ import {
   getKeypair,
   getProgramId,
   getPublicKey,
-  AUCTION_ACCOUNT_DATA_LAYOUT,
   writePublicKey,
   getTokenBalance,
   logAuction,
   getConnection,
+  getProgramConfigPubkey,
 } from "./utils";
 
 const exhibitor = async () => {
@@ -30,11 +31,46 @@ const exhibitor = async () => {
   console.log(`initial price = ${price}`);
   const duration = process.argv[3] || 3600;
   console.log(`auction duration = ${duration} sec`);
+  const reservePrice = process.argv[4] || 0;
+  console.log(`reserve price = ${reservePrice}`);
+  const minIncrement = process.argv[5] || 0;
+  const minIncrementBps = process.argv[6] || 0;
+  console.log(
+    `min increment = ${minIncrement}, min increment bps = ${minIncrementBps}`
+  );
+  const extensionWindow = process.argv[7] || 0;
+  const extensionSeconds = process.argv[8] || 0;
+  const maxExtensionSeconds = process.argv[9] || 0;
+  console.log(
+    `extension window = ${extensionWindow} sec, extension = ${extensionSeconds} sec, max extension = ${maxExtensionSeconds} sec`
+  );
+  const startAt = process.argv[10] || 0;
+  console.log(`start at = ${startAt}`);
+  const buyNowPrice = process.argv[11] || 0;
+  console.log(`buy now price = ${buyNowPrice}`);
+  // 0: English, 1: Dutch, 2: Sealed first-price, 3: Sealed second-price
+  const kind = Number(process.argv[12] || 0);
+  const floorPrice = process.argv[13] || 0;
+  // 0: Linear, 1: Stepwise, 2: Exponential
+  const decayCurve = Number(process.argv[14] || 0);
+  const decayInterval = process.argv[15] || 0;
+  const decayRateBps = process.argv[16] || 0;
+  console.log(
+    `kind = ${kind}, floor price = ${floorPrice}, decay curve = ${decayCurve}, decay interval = ${decayInterval} sec, decay rate bps = ${decayRateBps}`
+  );
+  const revealSeconds = process.argv[17] || 0;
+  const forfeitUnrevealed = Number(process.argv[18] || 0);
+  console.log(
+    `reveal duration = ${revealSeconds} sec, forfeit unrevealed = ${forfeitUnrevealed}`
+  );
+  const quantity = process.argv[19] || 1;
+  console.log(`quantity = ${quantity}`);
   const auctionProgramId = getProgramId();
   const exhibitorNftAccountPubkey = getPublicKey("exhibitor_nft_x");
   const nftMintPubkey = getPublicKey("mint_nft_x");
   const exhibitorKeypair = getKeypair("exhibitor");
   const exhibitorFtReceivingPubkey = getPublicKey("exhibitor_ft_nao");
+  const ftMintPubkey = getPublicKey("mint_ft_nao");
   const exhibitingNftTempAccountKeypair = new Keypair();
   const connection = getConnection();
 
@@ -55,22 +91,39 @@ const exhibitor = async () => {
     TOKEN_PROGRAM_ID
   );
 
-  // Here, a new escrow key pair is generated. Only the seller knows the escrow key pair because it is the seller who performs this process.
-  const escrowKeypair = new Keypair();
-  const createEscrowAccountIx = SystemProgram.createAccount({
-    space: AUCTION_ACCOUNT_DATA_LAYOUT.span,
-    lamports: await connection.getMinimumBalanceForRentExemption(
-      AUCTION_ACCOUNT_DATA_LAYOUT.span
-    ),
-    fromPubkey: exhibitorKeypair.publicKey,
-    newAccountPubkey: escrowKeypair.publicKey,
-    programId: auctionProgramId,
-  });
+  // The escrow account is a PDA derived from the auction house, the exhibitor and the NFT mint, and is created by the program.
+  // The auction outside of any house is seeded by the default pubkey.
+  const [escrowPubkey] = await PublicKey.findProgramAddress(
+    [
+      Buffer.from("auction"),
+      PublicKey.default.toBuffer(),
+      exhibitorKeypair.publicKey.toBuffer(),
+      nftMintPubkey.toBuffer(),
+    ],
+    auctionProgramId
+  );
+  // The fee config is a PDA derived from the FT mint, which the marketplace must have set before exhibiting.
+  const [feeConfigPubkey] = await PublicKey.findProgramAddress(
+    [Buffer.from("fee"), ftMintPubkey.toBuffer()],
+    auctionProgramId
+  );
+  // The metadata account of the NFT is given to save the creator royalties, which doesn't exist for the NFT without metadata.
+  const tokenMetadataProgramId = new PublicKey(
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s"
+  );
+  const [metadataPubkey] = await PublicKey.findProgramAddress(
+    [
+      Buffer.from("metadata"),
+      tokenMetadataProgramId.toBuffer(),
+      nftMintPubkey.toBuffer(),
+    ],
+    tokenMetadataProgramId
+  );
   // The programId, keys, and data passed here will be passed to the Solana Program.
   const exhibitIx = new TransactionInstruction({
     programId: auctionProgramId,
     keys: [
-      { pubkey: exhibitorKeypair.publicKey, isSigner: true, isWritable: false },
+      { pubkey: exhibitorKeypair.publicKey, isSigner: true, isWritable: true },
       { pubkey: exhibitorNftAccountPubkey, isSigner: false, isWritable: true },
       {
         pubkey: exhibitingNftTempAccountKeypair.publicKey,
@@ -78,16 +131,47 @@ const exhibitor = async () => {
         isWritable: true,
       },
       { pubkey: exhibitorFtReceivingPubkey, isSigner: false, isWritable: true },
-      { pubkey: escrowKeypair.publicKey, isSigner: false, isWritable: true },
+      { pubkey: ftMintPubkey, isSigner: false, isWritable: false },
+      { pubkey: escrowPubkey, isSigner: false, isWritable: true },
       { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
       { pubkey: SYSVAR_CLOCK_PUBKEY, isSigner: false, isWritable: false },
       { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      {
+        pubkey: SystemProgram.programId,
+        isSigner: false,
+        isWritable: false,
+      },
+      { pubkey: feeConfigPubkey, isSigner: false, isWritable: false },
+      { pubkey: metadataPubkey, isSigner: false, isWritable: false },
+      {
+        pubkey: await getProgramConfigPubkey(auctionProgramId),
+        isSigner: false,
+        isWritable: false,
+      },
     ],
     data: Buffer.from(
       Uint8Array.of(
         0,
         ...new BN(price).toArray("le", 8),
-        ...new BN(duration).toArray("le", 8)
+        ...new BN(duration).toArray("le", 8),
+        ...new BN(reservePrice).toArray("le", 8),
+        ...new Uint8Array(32), // no hidden reserve price
+        ...new BN(minIncrement).toArray("le", 8),
+        ...new BN(minIncrementBps).toArray("le", 2),
+        ...new BN(extensionWindow).toArray("le", 8),
+        ...new BN(extensionSeconds).toArray("le", 8),
+        ...new BN(maxExtensionSeconds).toArray("le", 8),
+        ...new BN(startAt).toArray("le", 8),
+        ...new BN(buyNowPrice).toArray("le", 8),
+        kind,
+        ...new BN(floorPrice).toArray("le", 8),
+        decayCurve,
+        ...new BN(decayInterval).toArray("le", 8),
+        ...new BN(decayRateBps).toArray("le", 2),
+        ...new BN(revealSeconds).toArray("le", 8),
+        forfeitUnrevealed,
+        ...new BN(quantity).toArray("le", 8),
+        0 // bids are paid in the FT, not in native SOL
       )
     ),
   });
@@ -95,14 +179,13 @@ const exhibitor = async () => {
   const tx = new Transaction().add(
     createTempNftAccountIx,
     initTempNftAccountIx,
-    createEscrowAccountIx,
     exhibitIx
   );
   console.log("Sending Exhibitor's transaction...");
   await connection.sendTransaction(
     tx,
     // A key pair is required for public keys that are set to 'isSigner: true' in the instruction.
-    [exhibitorKeypair, exhibitingNftTempAccountKeypair, escrowKeypair],
+    [exhibitorKeypair, exhibitingNftTempAccountKeypair],
     { skipPreflight: false, preflightCommitment: "confirmed" }
   );
 
@@ -117,7 +200,7 @@ const exhibitor = async () => {
     exhibitingNftTempAccountKeypair.publicKey,
     connection
   );
-  writePublicKey(escrowKeypair.publicKey, "escrow");
+  writePublicKey(escrowPubkey, "escrow");
 
   await logAuction(connection);
   const data = {
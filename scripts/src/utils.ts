@@ -73,6 +73,43 @@ export const AUCTION_ACCOUNT_DATA_LAYOUT = BufferLayout.struct<Auction>([
   BufferLayout.blob(32, "highestBidderPubkey"),
   BufferLayout.blob(32, "highestBidderFtTempPubkey"),
   BufferLayout.blob(32, "highestBidderFtReturningPubkey"),
+  BufferLayout.u8("bumpSeed"),
+  BufferLayout.blob(32, "ftMintPubkey"),
+  BufferLayout.blob(32, "nftMintPubkey"),
+  BufferLayout.blob(32, "highestBidderNftReceivingPubkey"),
+  BufferLayout.blob(32, "exhibitorNftPubkey"),
+  BufferLayout.blob(8, "reservePrice"),
+  BufferLayout.blob(32, "reservePriceHash"),
+  BufferLayout.blob(8, "minIncrement"),
+  BufferLayout.u16("minIncrementBps"),
+  BufferLayout.blob(8, "extensionWindow"),
+  BufferLayout.blob(8, "extensionSeconds"),
+  BufferLayout.blob(8, "maxEndAt"),
+  BufferLayout.blob(8, "startAt"),
+  BufferLayout.blob(8, "buyNowPrice"),
+  BufferLayout.u8("kind"),
+  BufferLayout.blob(8, "floorPrice"),
+  BufferLayout.u8("decayCurve"),
+  BufferLayout.blob(8, "decayInterval"),
+  BufferLayout.u16("decayRateBps"),
+  BufferLayout.blob(8, "revealEndAt"),
+  BufferLayout.u8("forfeitUnrevealed"),
+  BufferLayout.u32("bidCount"),
+  BufferLayout.blob(8, "secondPrice"),
+  BufferLayout.blob(8, "quantity"),
+  BufferLayout.u8("bundleLen"),
+  BufferLayout.blob(128, "bundleNftTempPubkeys"),
+  BufferLayout.blob(32, "vaultPubkey"),
+  BufferLayout.u16("feeBps"),
+  BufferLayout.blob(32, "treasuryPubkey"),
+  BufferLayout.u16("royaltyBps"),
+  BufferLayout.u8("creatorsLen"),
+  BufferLayout.blob(160, "creatorPubkeys"),
+  BufferLayout.blob(5, "creatorShares"),
+  BufferLayout.u16("referralBps"),
+  BufferLayout.u8("referralFromFee"),
+  BufferLayout.blob(32, "highestBidderReferrerPubkey"),
+  BufferLayout.blob(32, "auctionHousePubkey"),
 ]);
 
 export interface Auction {
@@ -85,6 +122,43 @@ export interface Auction {
   highestBidderPubkey: Uint8Array;
   highestBidderFtTempPubkey: Uint8Array;
   highestBidderFtReturningPubkey: Uint8Array;
+  bumpSeed: number;
+  ftMintPubkey: Uint8Array;
+  nftMintPubkey: Uint8Array;
+  highestBidderNftReceivingPubkey: Uint8Array;
+  exhibitorNftPubkey: Uint8Array;
+  reservePrice: Uint8Array;
+  reservePriceHash: Uint8Array;
+  minIncrement: Uint8Array;
+  minIncrementBps: number;
+  extensionWindow: Uint8Array;
+  extensionSeconds: Uint8Array;
+  maxEndAt: Uint8Array;
+  startAt: Uint8Array;
+  buyNowPrice: Uint8Array;
+  kind: number;
+  floorPrice: Uint8Array;
+  decayCurve: number;
+  decayInterval: Uint8Array;
+  decayRateBps: number;
+  revealEndAt: Uint8Array;
+  forfeitUnrevealed: number;
+  bidCount: number;
+  secondPrice: Uint8Array;
+  quantity: Uint8Array;
+  bundleLen: number;
+  bundleNftTempPubkeys: Uint8Array;
+  vaultPubkey: Uint8Array;
+  feeBps: number;
+  treasuryPubkey: Uint8Array;
+  royaltyBps: number;
+  creatorsLen: number;
+  creatorPubkeys: Uint8Array;
+  creatorShares: Uint8Array;
+  referralBps: number;
+  referralFromFee: number;
+  highestBidderReferrerPubkey: Uint8Array;
+  auctionHousePubkey: Uint8Array;
 }
 
 export async function logAuction(connection: Connection) {
@@ -134,8 +208,17 @@ export async function getCurrentAuction(
     encodedAuctionState
   ) as Auction;
   const pda = await PublicKey.findProgramAddress(
-    [Buffer.from("escrow")],
+    [Buffer.from("escrow"), escrowPubkey.toBuffer()],
     auctionProgramId
   );
   return { pda, auction };
 }
+
+// The program config is given last to Exhibit, Bid, CommitBid, BuyNow and Buy, which are rejected while the admin pauses the program.
+export async function getProgramConfigPubkey(auctionProgramId: PublicKey) {
+  const [configPubkey] = await PublicKey.findProgramAddress(
+    [Buffer.from("config")],
+    auctionProgramId
+  );
+  return configPubkey;
+}